@@ -0,0 +1,145 @@
+use std::{
+    sync::{Condvar, Mutex, MutexGuard, PoisonError},
+    time::Duration,
+};
+
+/**
+Blocks a background thread until a timeout elapses or it is notified
+
+This is what the [`Scheduler`](crate::Scheduler)'s timing thread, the
+[`ExpiryNotifier`](crate::ExpiryNotifier)'s thread, and the thread that wakes async timers wait on.
+It works like a [`Condvar`] with a single waiter.
+
+With the `os-timers` feature on Linux, the timeout is kept by a `timerfd` and notifications
+are sent through an `eventfd`. If the OS objects cannot be created, it uses a [`Condvar`].
+BSD/macOS `kqueue` timers and Windows waitable timers are not implemented, because their bindings
+need unsafe code, which this crate does not allow, so those platforms always use a [`Condvar`].
+*/
+pub(crate) struct Alarm {
+    condvar: Condvar,
+    #[cfg(all(feature = "os-timers", target_os = "linux"))]
+    os: Option<os::OsAlarm>,
+}
+
+impl Alarm {
+    pub(crate) fn new() -> Alarm {
+        Alarm {
+            condvar: Condvar::new(),
+            #[cfg(all(feature = "os-timers", target_os = "linux"))]
+            os: os::OsAlarm::new().ok(),
+        }
+    }
+    /// Unlocks the mutex and waits until the timeout elapses or [`Alarm::notify`] is called,
+    /// then locks the mutex again
+    ///
+    /// Like a [`Condvar`], this may return early.
+    pub(crate) fn wait<'a, T>(
+        &self,
+        mutex: &'a Mutex<T>,
+        guard: MutexGuard<'a, T>,
+        timeout: Option<Duration>,
+    ) -> MutexGuard<'a, T> {
+        if timeout.is_some_and(|timeout| timeout.is_zero()) {
+            return guard;
+        }
+        // If the timer cannot be armed, the Condvar is used so that callers do not spin
+        #[cfg(all(feature = "os-timers", target_os = "linux"))]
+        if let Some(os) = self.os.as_ref().filter(|os| os.arm(timeout)) {
+            drop(guard);
+            os.wait();
+            return mutex.lock().unwrap_or_else(PoisonError::into_inner);
+        }
+        #[cfg(not(all(feature = "os-timers", target_os = "linux")))]
+        let _ = mutex;
+        match timeout {
+            Some(timeout) => {
+                self.condvar
+                    .wait_timeout(guard, timeout)
+                    .unwrap_or_else(PoisonError::into_inner)
+                    .0
+            }
+            None => self
+                .condvar
+                .wait(guard)
+                .unwrap_or_else(PoisonError::into_inner),
+        }
+    }
+    /// Wakes the waiting thread
+    ///
+    /// If no thread is waiting, the next call to [`Alarm::wait`] may return immediately.
+    pub(crate) fn notify(&self) {
+        #[cfg(all(feature = "os-timers", target_os = "linux"))]
+        if let Some(os) = &self.os {
+            os.notify();
+        }
+        // The waiter uses the Condvar if its timer could not be armed
+        self.condvar.notify_one();
+    }
+}
+
+#[cfg(all(feature = "os-timers", target_os = "linux"))]
+mod os {
+    use std::{convert::TryFrom, io, os::fd::OwnedFd, time::Duration};
+
+    use rustix::{
+        event::{eventfd, poll, EventfdFlags, PollFd, PollFlags},
+        time::{
+            timerfd_create, timerfd_settime, Itimerspec, TimerfdClockId, TimerfdFlags,
+            TimerfdTimerFlags, Timespec,
+        },
+    };
+
+    pub(super) struct OsAlarm {
+        timer: OwnedFd,
+        event: OwnedFd,
+    }
+
+    impl OsAlarm {
+        pub(super) fn new() -> io::Result<OsAlarm> {
+            Ok(OsAlarm {
+                timer: timerfd_create(
+                    TimerfdClockId::Monotonic,
+                    TimerfdFlags::CLOEXEC | TimerfdFlags::NONBLOCK,
+                )?,
+                event: eventfd(0, EventfdFlags::CLOEXEC | EventfdFlags::NONBLOCK)?,
+            })
+        }
+        /// Sets the timer to fire after the timeout, or disarms it if there is none
+        ///
+        /// The timeout must not be zero, since that would disarm the timer instead.
+        /// Returns `false` if the timer could not be set.
+        pub(super) fn arm(&self, timeout: Option<Duration>) -> bool {
+            let zero = Timespec {
+                tv_sec: 0,
+                tv_nsec: 0,
+            };
+            let value = match timeout {
+                Some(timeout) => Timespec::try_from(timeout).unwrap_or(Timespec {
+                    tv_sec: i64::MAX,
+                    tv_nsec: 0,
+                }),
+                None => zero,
+            };
+            // Setting the timer also clears any expiration that was not read
+            let spec = Itimerspec {
+                it_interval: zero,
+                it_value: value,
+            };
+            timerfd_settime(&self.timer, TimerfdTimerFlags::empty(), &spec).is_ok()
+        }
+        /// Waits until the armed timer fires or a notification arrives
+        pub(super) fn wait(&self) {
+            let mut fds = [
+                PollFd::new(&self.timer, PollFlags::IN),
+                PollFd::new(&self.event, PollFlags::IN),
+            ];
+            // Errors such as interruptions count as early returns
+            let _ = poll(&mut fds, None);
+            let mut buf = [0; 8];
+            let _ = rustix::io::read(&self.event, &mut buf);
+        }
+        pub(super) fn notify(&self) {
+            let _ = rustix::io::write(&self.event, &1u64.to_ne_bytes());
+        }
+    }
+}
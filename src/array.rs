@@ -0,0 +1,322 @@
+use std::time::{Duration, Instant};
+
+use crate::{Entry, ExpiredHint, Key, Timer, ToDuration};
+
+/**
+A fixed-capacity version of [`TimedList`](crate::TimedList) that never allocates
+
+Elements are stored inline in an array of `N` slots. Slots of timed-out
+elements are reused by later insertions, so elements are not necessarily
+iterated over in the order they were inserted.
+
+[`TimedArray`] is meant for hot loops where allocation is not allowed. It still needs `std`
+for [`Instant`], so it is not usable in `no_std` environments. It only has the core of the
+[`TimedList`](crate::TimedList) API: there is no [`ListOrder`](crate::ListOrder),
+[`Eviction`](crate::Eviction) policy, [`CleanPolicy`](crate::CleanPolicy), or expiration callback.
+Inserting into a full array gives the element back, and timed-out elements are only removed
+when their slots are reused, when iterating mutably, or when [`TimedArray::clean`] is called.
+
+# Example
+```
+use eggtimer::TimedArray;
+
+let mut array: TimedArray<&str, 2> = TimedArray::new();
+let key = array.insert("a", 60.0).unwrap();
+array.insert("b", 60.0).unwrap();
+assert_eq!(array.insert("c", 60.0), Err("c"));
+assert_eq!(array.remove(key), Some("a"));
+assert_eq!(array.len(), 1);
+```
+*/
+#[derive(Debug, Clone)]
+pub struct TimedArray<T, const N: usize> {
+    slots: [Option<Entry<T>>; N],
+    /// The deadlines of the occupied slots, sorted, in the first `tracked` elements
+    deadlines: [Instant; N],
+    tracked: usize,
+    expired: ExpiredHint,
+    next_key: u64,
+}
+
+impl<T, const N: usize> TimedArray<T, N> {
+    /// Creates a new empty [`TimedArray`]
+    pub fn new() -> TimedArray<T, N> {
+        TimedArray {
+            slots: std::array::from_fn(|_| None),
+            deadlines: [Instant::now(); N],
+            tracked: 0,
+            expired: ExpiredHint::default(),
+            next_key: 0,
+        }
+    }
+    /// Gets the maximum number of elements the array can hold
+    pub fn capacity(&self) -> usize {
+        N
+    }
+    /// Inserts an element into the array with the given number of floating-point seconds
+    ///
+    /// Returns a [`Key`] that can be used to refer to the element later,
+    /// or gives the element back if every slot holds an element that has not timed out.
+    pub fn insert<D: ToDuration>(&mut self, element: T, time: D) -> Result<Key, T> {
+        let now = Instant::now();
+        let i = self.slots.iter().position(|slot| match slot {
+            Some(entry) => entry.timer.ends_at() < now,
+            None => true,
+        });
+        let i = match i {
+            Some(i) => i,
+            None => return Err(element),
+        };
+        if let Some(old) = self.slots[i].take() {
+            self.untrack(old.timer.ends_at());
+        }
+        let key = Key(self.next_key);
+        self.next_key += 1;
+        let timer = Timer::set(time);
+        self.track(timer.ends_at());
+        self.slots[i] = Some(Entry {
+            key,
+            timer,
+            elem: element,
+        });
+        Ok(key)
+    }
+    /// Removes the element with the given [`Key`] from the array and returns it
+    ///
+    /// Returns `None` if the element has timed out or was already removed.
+    pub fn remove(&mut self, key: Key) -> Option<T> {
+        let i = self.position(key)?;
+        let entry = self.take(i)?;
+        Some(entry.elem)
+    }
+    /// Gets a reference to the element at the given index among elements that have not timed out
+    pub fn get(&self, index: usize) -> Option<&T> {
+        self.iter().nth(index)
+    }
+    /// Gets a mutable reference to the element at the given index among elements that have not timed out
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        let now = Instant::now();
+        self.slots
+            .iter_mut()
+            .flatten()
+            .filter(|entry| entry.timer.ends_at() >= now)
+            .nth(index)
+            .map(|entry| &mut entry.elem)
+    }
+    /// Gets a reference to the element that was inserted first among elements that have not timed out
+    pub fn first(&self) -> Option<&T> {
+        self.live_entries()
+            .min_by_key(|entry| entry.key)
+            .map(|entry| &entry.elem)
+    }
+    /// Gets a reference to the element that was inserted last among elements that have not timed out
+    pub fn last(&self) -> Option<&T> {
+        self.live_entries()
+            .max_by_key(|entry| entry.key)
+            .map(|entry| &entry.elem)
+    }
+    /// Adds time to the element with the given [`Key`]
+    ///
+    /// Returns `false` if the element has timed out or was already removed.
+    pub fn extend_ttl<D: ToDuration>(&mut self, key: Key, time: D) -> bool {
+        self.adjust(key, |timer| timer.duration += time.to_duration())
+    }
+    /// Restarts the countdown of the element with the given [`Key`] from its original [`Duration`]
+    ///
+    /// Returns `false` if the element has timed out or was already removed.
+    pub fn touch(&mut self, key: Key) -> bool {
+        self.adjust(key, Timer::reset)
+    }
+    /// Gets the time left for the element with the given [`Key`]
+    ///
+    /// Returns `None` if the element has timed out or was already removed.
+    pub fn remaining(&self, key: Key) -> Option<Duration> {
+        self.slots
+            .iter()
+            .flatten()
+            .find(|entry| entry.key == key)
+            .and_then(|entry| entry.timer.duration_left())
+    }
+    /// Forces the removal of all elements whose [`Duration`] has elpased.
+    pub fn clean(&mut self) {
+        let now = Instant::now();
+        for slot in &mut self.slots {
+            if slot
+                .as_ref()
+                .is_some_and(|entry| entry.timer.ends_at() < now)
+            {
+                *slot = None;
+            }
+        }
+        let expired = self.deadlines[..self.tracked].partition_point(|deadline| *deadline < now);
+        self.deadlines[..self.tracked].rotate_left(expired);
+        self.tracked -= expired;
+        self.expired.reset();
+    }
+    /// Removes all elements from the array
+    pub fn clear(&mut self) {
+        for slot in &mut self.slots {
+            *slot = None;
+        }
+        self.tracked = 0;
+        self.expired.reset();
+    }
+    /// Gets the number of elements in the array that have not timed out.
+    ///
+    /// This runs in constant time unless elements have timed out since the last call,
+    /// in which case it takes logarithmic time to find how many.
+    pub fn len(&self) -> usize {
+        self.tracked - self.expired.count(&self.deadlines[..self.tracked])
+    }
+    /// Check if the array is empty or if all existing elements have timed out.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+    /// Retains elements in the array that match the predicate
+    pub fn retain<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&T) -> bool,
+    {
+        for i in 0..N {
+            if self.slots[i].as_ref().is_some_and(|entry| !f(&entry.elem)) {
+                self.take(i);
+            }
+        }
+    }
+    /// Iterates immutably through all elements.
+    ///
+    /// While this method does not remove timed-out elements,
+    /// it does filter them out.
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = &T> {
+        self.timer_iter().map(|(elem, _)| elem)
+    }
+    /// Iterates mutably through all elements.
+    pub fn iter_mut(&mut self) -> impl DoubleEndedIterator<Item = &mut T> {
+        self.timer_iter_mut().map(|(elem, _)| elem)
+    }
+    /// Iterates immutably through all elements and their timers.
+    ///
+    /// While this method does not remove timed-out elements,
+    /// it does filter them out.
+    pub fn timer_iter(&self) -> impl DoubleEndedIterator<Item = (&T, Timer)> {
+        self.slots.iter().flatten().filter_map(|entry| {
+            if entry.timer.is_ready() {
+                None
+            } else {
+                Some((&entry.elem, entry.timer))
+            }
+        })
+    }
+    /// Iterates mutably through all elements and their timers.
+    pub fn timer_iter_mut(&mut self) -> impl DoubleEndedIterator<Item = (&mut T, Timer)> {
+        self.clean();
+        self.slots.iter_mut().flatten().filter_map(|entry| {
+            if entry.timer.is_ready() {
+                None
+            } else {
+                Some((&mut entry.elem, entry.timer))
+            }
+        })
+    }
+    /// Iterates through the entries that have not timed out
+    fn live_entries(&self) -> impl Iterator<Item = &Entry<T>> {
+        let now = Instant::now();
+        self.slots
+            .iter()
+            .flatten()
+            .filter(move |entry| entry.timer.ends_at() >= now)
+    }
+    /// Finds the slot of the live element with the given [`Key`]
+    fn position(&self, key: Key) -> Option<usize> {
+        self.slots.iter().position(|slot| {
+            slot.as_ref()
+                .is_some_and(|entry| entry.key == key && !entry.timer.is_ready())
+        })
+    }
+    /// Changes the timer of the live element with the given [`Key`]
+    fn adjust<F: FnOnce(&mut Timer)>(&mut self, key: Key, f: F) -> bool {
+        let i = match self.position(key) {
+            Some(i) => i,
+            None => return false,
+        };
+        let mut entry = match self.take(i) {
+            Some(entry) => entry,
+            None => return false,
+        };
+        f(&mut entry.timer);
+        self.track(entry.timer.ends_at());
+        self.slots[i] = Some(entry);
+        true
+    }
+    /// Empties the slot at the given index
+    fn take(&mut self, i: usize) -> Option<Entry<T>> {
+        let entry = self.slots[i].take()?;
+        self.untrack(entry.timer.ends_at());
+        Some(entry)
+    }
+    /// Adds a deadline to the sorted deadlines
+    fn track(&mut self, deadline: Instant) {
+        let i = self.deadlines[..self.tracked].partition_point(|d| *d <= deadline);
+        self.deadlines[i..=self.tracked].rotate_right(1);
+        self.deadlines[i] = deadline;
+        self.tracked += 1;
+    }
+    /// Removes a deadline from the sorted deadlines
+    fn untrack(&mut self, deadline: Instant) {
+        if let Ok(i) = self.deadlines[..self.tracked].binary_search(&deadline) {
+            self.deadlines[i..self.tracked].rotate_left(1);
+            self.tracked -= 1;
+            self.expired.removed(i);
+        }
+    }
+}
+
+impl<T, const N: usize> Default for TimedArray<T, N> {
+    fn default() -> Self {
+        TimedArray::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn full_arrays_reject_and_reuse_timed_out_slots() {
+        let mut array: TimedArray<char, 2> = TimedArray::new();
+        let a = array.insert('a', 0.0).unwrap();
+        array.insert('b', 60.0).unwrap();
+        std::thread::sleep(Duration::from_millis(1));
+        assert_eq!(array.len(), 1);
+        let c = array.insert('c', 60.0).unwrap();
+        assert_eq!(array.insert('d', 60.0), Err('d'));
+        assert_eq!(array.remove(a), None);
+        assert_eq!(array.len(), 2);
+        assert_eq!(array.first(), Some(&'b'));
+        assert_eq!(array.last(), Some(&'c'));
+        assert_eq!(array.remove(c), Some('c'));
+        assert_eq!(array.len(), 1);
+        assert_eq!(array.get(0), Some(&'b'));
+        assert_eq!(array.get(1), None);
+    }
+
+    #[test]
+    fn len_tracks_adjustments() {
+        let mut array: TimedArray<u8, 4> = TimedArray::new();
+        let a = array.insert(1, 0.0).unwrap();
+        let b = array.insert(2, 60.0).unwrap();
+        let c = array.insert(3, 60.0).unwrap();
+        assert!(array.extend_ttl(b, 60.0));
+        std::thread::sleep(Duration::from_millis(1));
+        assert_eq!(array.len(), 2);
+        assert!(!array.touch(a));
+        assert!(array.touch(c));
+        assert_eq!(array.len(), 2);
+        array.clean();
+        assert_eq!(array.len(), 2);
+        array.retain(|x| *x != 2);
+        assert_eq!(array.len(), 1);
+        array.clear();
+        assert!(array.is_empty());
+    }
+}
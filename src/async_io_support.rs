@@ -0,0 +1,42 @@
+use crate::{Ticker, Timer};
+
+impl Timer {
+    /**
+    Waits asynchronously until the [`Timer`] is ready, using `async-io`'s timers
+
+    This works with `smol` and any other runtime built on `async-io`.
+    Returns immediately if the [`Timer`] is already ready.
+
+    # Example
+    ```
+    use eggtimer::Timer;
+
+    async_io::block_on(async {
+        let timer = Timer::set(0.01);
+        timer.ready_async_io().await;
+        assert!(timer.is_ready());
+    });
+    ```
+    */
+    pub async fn ready_async_io(self) {
+        if !self.is_ready() {
+            async_io::Timer::at(self.ends_at()).await;
+        }
+    }
+}
+
+impl Ticker {
+    /// Waits asynchronously for the next tick using `async-io`'s timers,
+    /// then returns the number of ticks that have happened since the last call to this or [`Ticker::ticks`]
+    ///
+    /// The result is always at least 1.
+    pub async fn tick_async_io(&mut self) -> u64 {
+        loop {
+            async_io::Timer::after(self.time_until_next()).await;
+            let ticks = self.ticks();
+            if ticks > 0 {
+                return ticks;
+            }
+        }
+    }
+}
@@ -0,0 +1,130 @@
+use std::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+    time::Instant,
+};
+
+use crate::{Ticker, Timer};
+
+type BoxedSleep = Pin<Box<dyn Future<Output = ()> + Send>>;
+
+impl Timer {
+    /**
+    Waits asynchronously until the [`Timer`] is ready, using the `async-std` runtime's timers
+
+    Returns immediately if the [`Timer`] is already ready.
+
+    # Example
+    ```
+    use eggtimer::Timer;
+
+    async_std::task::block_on(async {
+        let timer = Timer::set(0.01);
+        timer.ready_async_std().await;
+        assert!(timer.is_ready());
+    });
+    ```
+    */
+    pub async fn ready_async_std(self) {
+        // Sleeping again for whatever is left handles timers that wake slightly early
+        while let Some(left) = self.duration_left() {
+            async_std::task::sleep(left).await;
+        }
+    }
+}
+
+impl Ticker {
+    /// Waits asynchronously for the next tick using the `async-std` runtime's timers,
+    /// then returns the number of ticks that have happened since the last call to this or [`Ticker::ticks`]
+    ///
+    /// The result is always at least 1.
+    pub async fn tick_async_std(&mut self) -> u64 {
+        loop {
+            async_std::task::sleep(self.time_until_next()).await;
+            let ticks = self.ticks();
+            if ticks > 0 {
+                return ticks;
+            }
+        }
+    }
+    /**
+    Turns the [`Ticker`] into a stream that is driven by the `async-std` runtime's timers
+
+    # Example
+    ```
+    use async_std::stream::StreamExt;
+    use eggtimer::Ticker;
+
+    async_std::task::block_on(async {
+        let mut ticks = Ticker::new(0.01).into_async_std_stream();
+        assert!(ticks.next().await.unwrap() >= 1);
+    });
+    ```
+    */
+    pub fn into_async_std_stream(self) -> AsyncStdTicks {
+        AsyncStdTicks {
+            ticker: self,
+            sleep: None,
+        }
+    }
+}
+
+/// A stream of the ticks of a [`Ticker`], driven by the `async-std` runtime's timers
+///
+/// This is created by [`Ticker::into_async_std_stream`]. It yields the number of ticks
+/// that have happened since the last one was yielded, and never ends.
+/// It always sleeps with `async-std`, even when the `async-io` feature is also enabled.
+pub struct AsyncStdTicks {
+    ticker: Ticker,
+    /// The pending sleep and the tick it ends at
+    sleep: Option<(Instant, BoxedSleep)>,
+}
+
+impl AsyncStdTicks {
+    /// Gets the underlying [`Ticker`]
+    pub fn ticker(&self) -> &Ticker {
+        &self.ticker
+    }
+    /// Turns the stream back into its [`Ticker`]
+    pub fn into_ticker(self) -> Ticker {
+        self.ticker
+    }
+}
+
+impl futures_core::Stream for AsyncStdTicks {
+    type Item = u64;
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<u64>> {
+        let this = &mut *self;
+        loop {
+            let ticks = this.ticker.ticks();
+            if ticks > 0 {
+                this.sleep = None;
+                return Poll::Ready(Some(ticks));
+            }
+            let next_tick = this.ticker.next_tick();
+            let sleep = match &mut this.sleep {
+                Some((deadline, sleep)) if *deadline == next_tick => sleep,
+                sleep => {
+                    let left = next_tick.saturating_duration_since(Instant::now());
+                    &mut sleep
+                        .insert((next_tick, Box::pin(async_std::task::sleep(left))))
+                        .1
+                }
+            };
+            if sleep.as_mut().poll(cx).is_pending() {
+                return Poll::Pending;
+            }
+            // The sleep can end slightly before the tick, so check again with a fresh one
+            this.sleep = None;
+        }
+    }
+}
+
+impl std::fmt::Debug for AsyncStdTicks {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_struct("AsyncStdTicks")
+            .field("ticker", &self.ticker)
+            .finish()
+    }
+}
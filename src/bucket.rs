@@ -0,0 +1,91 @@
+use std::{
+    thread,
+    time::{Duration, Instant},
+};
+
+/// A token bucket rate limiter
+///
+/// Tokens are added to the bucket at a constant rate, up to a maximum burst size.
+/// Each allowed action takes one token, so bursts of actions are allowed up to
+/// the burst size while the long-run rate never exceeds the refill rate.
+/// The bucket starts full.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct TokenBucket {
+    per_second: f64,
+    burst: f64,
+    tokens: f64,
+    last: Instant,
+}
+
+impl TokenBucket {
+    /// Creates a new full [`TokenBucket`] that refills at the given number of tokens
+    /// per second and holds at most `burst` tokens
+    ///
+    /// # Panics
+    ///
+    /// Panics if `per_second` is not positive and finite or if `burst` is 0
+    pub fn new(per_second: f64, burst: u32) -> TokenBucket {
+        assert!(
+            per_second > 0.0 && per_second.is_finite(),
+            "TokenBucket rate must be positive and finite"
+        );
+        assert!(burst > 0, "TokenBucket burst size must be at least 1");
+        TokenBucket {
+            per_second,
+            burst: f64::from(burst),
+            tokens: f64::from(burst),
+            last: Instant::now(),
+        }
+    }
+    /// Gets the number of tokens added per second
+    pub fn rate(&self) -> f64 {
+        self.per_second
+    }
+    /// Gets the maximum number of tokens the bucket can hold
+    pub fn burst(&self) -> u32 {
+        self.burst as u32
+    }
+    /// Gets the number of tokens currently in the bucket
+    pub fn available(&mut self) -> f64 {
+        self.refill();
+        self.tokens
+    }
+    /// Takes a token if one is available
+    ///
+    /// Returns `false` if the bucket is empty.
+    pub fn try_take(&mut self) -> bool {
+        self.refill();
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            true
+        } else {
+            false
+        }
+    }
+    /// Gets the time until a token will be available
+    pub fn time_until_available(&mut self) -> Duration {
+        self.refill();
+        if self.tokens >= 1.0 {
+            Duration::from_secs(0)
+        } else {
+            Duration::from_secs_f64((1.0 - self.tokens) / self.per_second)
+        }
+    }
+    /// Takes a token, blocking the current thread until one is available
+    pub fn take(&mut self) {
+        while !self.try_take() {
+            thread::sleep(self.time_until_available());
+        }
+    }
+    /// Adds the tokens accumulated since the last refill
+    fn refill(&mut self) {
+        let now = Instant::now();
+        self.tokens = self.tokens_at(now);
+        self.last = now;
+    }
+    /// Gets the number of tokens the bucket will hold at the given [`Instant`] if none are taken
+    pub(crate) fn tokens_at(&self, now: Instant) -> f64 {
+        let elapsed = now.saturating_duration_since(self.last).as_secs_f64();
+        (self.tokens + elapsed * self.per_second).min(self.burst)
+    }
+}
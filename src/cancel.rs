@@ -0,0 +1,223 @@
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+use crate::{Timer, ToDuration};
+
+struct Inner {
+    cancelled: AtomicBool,
+    timer: Option<Timer>,
+    #[cfg(feature = "async")]
+    wakers: std::sync::Mutex<Wakers>,
+}
+
+/// The wakers of the futures waiting for a [`CancelToken`] to be cancelled, keyed by slot id
+#[cfg(feature = "async")]
+#[derive(Default)]
+struct Wakers {
+    wakers: std::collections::BTreeMap<u64, std::task::Waker>,
+    next_id: u64,
+}
+
+/**
+A token for cooperatively cancelling long operations
+
+A [`CancelToken`] is cancelled either explicitly with [`CancelToken::cancel`]
+or when its [`Timer`] runs out. Clones share the same state, so one can be handed
+to a worker while another is kept to cancel it.
+
+With the `async` feature, [`CancelToken::cancelled`] can be awaited.
+
+# Example
+```
+use eggtimer::CancelToken;
+
+let token = CancelToken::with_timeout(30.0);
+let worker_token = token.clone();
+assert!(!worker_token.is_cancelled());
+token.cancel();
+assert!(worker_token.is_cancelled());
+```
+*/
+#[derive(Clone)]
+pub struct CancelToken {
+    inner: Arc<Inner>,
+}
+
+impl CancelToken {
+    /// Creates a new [`CancelToken`] that is only cancelled explicitly
+    pub fn new() -> CancelToken {
+        CancelToken::from_timer(None)
+    }
+    /// Creates a new [`CancelToken`] that is cancelled after the given number of floating-point seconds
+    pub fn with_timeout<D: ToDuration>(time: D) -> CancelToken {
+        CancelToken::from_timer(Some(Timer::set(time)))
+    }
+    fn from_timer(timer: Option<Timer>) -> CancelToken {
+        CancelToken {
+            inner: Arc::new(Inner {
+                cancelled: AtomicBool::new(false),
+                timer,
+                #[cfg(feature = "async")]
+                wakers: Default::default(),
+            }),
+        }
+    }
+    /// Cancels the token and all of its clones
+    pub fn cancel(&self) {
+        self.inner.cancelled.store(true, Ordering::Release);
+        #[cfg(feature = "async")]
+        {
+            let wakers = std::mem::take(&mut self.inner.lock_wakers().wakers);
+            for waker in wakers.into_values() {
+                waker.wake();
+            }
+        }
+    }
+    /// Checks if the token has been cancelled or its [`Timer`] has run out
+    pub fn is_cancelled(&self) -> bool {
+        self.inner.cancelled.load(Ordering::Acquire)
+            || self.inner.timer.is_some_and(|timer| timer.is_ready())
+    }
+    /// Gets the time left before the token's [`Timer`] runs out
+    ///
+    /// Returns `None` if the token has no [`Timer`], and zero if the token has been cancelled.
+    pub fn remaining(&self) -> Option<Duration> {
+        let timer = self.inner.timer?;
+        if self.inner.cancelled.load(Ordering::Acquire) {
+            Some(Duration::from_secs(0))
+        } else {
+            Some(timer.duration_left().unwrap_or_default())
+        }
+    }
+    /// Waits asynchronously until the token is cancelled
+    ///
+    /// This does not depend on any particular async runtime.
+    #[cfg(feature = "async")]
+    pub async fn cancelled(&self) {
+        let mut waiting = Waiting {
+            token: self,
+            slot: None,
+            registration: crate::waker::Registration::default(),
+        };
+        std::future::poll_fn(|cx| waiting.poll(cx)).await
+    }
+}
+
+#[cfg(feature = "async")]
+impl Inner {
+    fn lock_wakers(&self) -> std::sync::MutexGuard<'_, Wakers> {
+        self.wakers
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+}
+
+/// The state of a [`CancelToken::cancelled`] future
+///
+/// Its [`Waker`](std::task::Waker) is kept in one slot of the token's list and one
+/// [`Registration`](crate::waker::Registration) for the token's deadline, both of which are
+/// updated in place when it is polled again and removed when it is dropped.
+#[cfg(feature = "async")]
+struct Waiting<'a> {
+    token: &'a CancelToken,
+    slot: Option<u64>,
+    registration: crate::waker::Registration,
+}
+
+#[cfg(feature = "async")]
+impl Waiting<'_> {
+    fn poll(&mut self, cx: &mut std::task::Context<'_>) -> std::task::Poll<()> {
+        if self.token.is_cancelled() {
+            return std::task::Poll::Ready(());
+        }
+        let mut wakers = self.token.inner.lock_wakers();
+        match self.slot.and_then(|slot| wakers.wakers.get_mut(&slot)) {
+            Some(registered) => {
+                if !registered.will_wake(cx.waker()) {
+                    *registered = cx.waker().clone();
+                }
+            }
+            None => {
+                let slot = wakers.next_id;
+                wakers.next_id += 1;
+                wakers.wakers.insert(slot, cx.waker().clone());
+                self.slot = Some(slot);
+            }
+        }
+        drop(wakers);
+        if let Some(timer) = self.token.inner.timer {
+            self.registration.register(timer.ends_at(), cx.waker());
+        }
+        if self.token.is_cancelled() {
+            std::task::Poll::Ready(())
+        } else {
+            std::task::Poll::Pending
+        }
+    }
+}
+
+#[cfg(feature = "async")]
+impl Drop for Waiting<'_> {
+    fn drop(&mut self) {
+        if let Some(slot) = self.slot {
+            self.token.inner.lock_wakers().wakers.remove(&slot);
+        }
+    }
+}
+
+impl Default for CancelToken {
+    fn default() -> Self {
+        CancelToken::new()
+    }
+}
+
+impl std::fmt::Debug for CancelToken {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_struct("CancelToken")
+            .field("cancelled", &self.is_cancelled())
+            .field("timer", &self.inner.timer)
+            .finish()
+    }
+}
+
+#[cfg(all(test, feature = "async"))]
+mod tests {
+    use std::{future::Future, pin::pin, task::Context};
+
+    use super::*;
+    use crate::waker::tests::noop_waker;
+
+    fn waiting(token: &CancelToken) -> usize {
+        token.inner.lock_wakers().wakers.len()
+    }
+
+    #[test]
+    fn dropped_futures_do_not_accumulate_wakers() {
+        let waker = noop_waker();
+        let mut cx = Context::from_waker(&waker);
+        // A token without a timer stays off the shared waker thread, which other tests count
+        let token = CancelToken::new();
+        for _ in 0..100 {
+            let mut fut = pin!(token.cancelled());
+            assert!(fut.as_mut().poll(&mut cx).is_pending());
+        }
+        assert_eq!(waiting(&token), 0);
+        let mut fut = Box::pin(token.cancelled());
+        for _ in 0..100 {
+            assert!(fut.as_mut().poll(&mut cx).is_pending());
+        }
+        assert_eq!(waiting(&token), 1);
+        drop(fut);
+        assert_eq!(waiting(&token), 0);
+        let mut fut = Box::pin(token.cancelled());
+        assert!(fut.as_mut().poll(&mut cx).is_pending());
+        token.cancel();
+        assert_eq!(waiting(&token), 0);
+        assert!(fut.as_mut().poll(&mut cx).is_ready());
+    }
+}
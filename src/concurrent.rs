@@ -0,0 +1,256 @@
+use std::{
+    borrow::Borrow,
+    collections::{hash_map::RandomState, HashMap},
+    hash::{BuildHasher, Hash},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        PoisonError, RwLock, RwLockReadGuard, RwLockWriteGuard,
+    },
+    time::{Duration, Instant},
+};
+
+use crate::{Timer, ToDuration};
+
+#[derive(Debug)]
+struct Shard<K, V> {
+    map: RwLock<HashMap<K, (Timer, V)>>,
+    /// The number of insertions into the shard since it was last cleaned
+    inserts: AtomicUsize,
+}
+
+impl<K, V> Shard<K, V> {
+    fn new() -> Shard<K, V> {
+        Shard {
+            map: RwLock::new(HashMap::new()),
+            inserts: AtomicUsize::new(0),
+        }
+    }
+    fn write(&self) -> RwLockWriteGuard<'_, HashMap<K, (Timer, V)>> {
+        self.map.write().unwrap_or_else(PoisonError::into_inner)
+    }
+    /// Removes the shard's timed-out entries while its write lock is held
+    fn clean(&self, map: &mut HashMap<K, (Timer, V)>) {
+        let now = Instant::now();
+        map.retain(|_, (timer, _)| timer.ends_at() >= now);
+        self.inserts.store(0, Ordering::Relaxed);
+    }
+}
+
+const DEFAULT_SHARDS: usize = 16;
+
+/// A thread-safe map where each entry has an associated [`Duration`]
+///
+/// Entries are split across several independently locked shards, so threads working
+/// with different keys rarely block each other, and lookups only need a shared lock.
+/// Timed-out entries are never returned. They are removed when their key is reused,
+/// when [`ConcurrentTimedMap::clean`] is called, or from a shard that an insertion
+/// would otherwise have to grow if enough entries have been inserted into it since it was last cleaned.
+#[derive(Debug)]
+pub struct ConcurrentTimedMap<K, V> {
+    shards: Vec<Shard<K, V>>,
+    hasher: RandomState,
+}
+
+impl<K, V> ConcurrentTimedMap<K, V>
+where
+    K: Hash + Eq,
+{
+    /// Creates a new [`ConcurrentTimedMap`]
+    pub fn new() -> ConcurrentTimedMap<K, V> {
+        ConcurrentTimedMap::with_shards(DEFAULT_SHARDS)
+    }
+    /// Creates a new [`ConcurrentTimedMap`] with the given number of shards
+    ///
+    /// More shards means less contention between threads at the cost of more memory.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of shards is 0
+    pub fn with_shards(shards: usize) -> ConcurrentTimedMap<K, V> {
+        assert!(
+            shards > 0,
+            "ConcurrentTimedMap must have at least one shard"
+        );
+        ConcurrentTimedMap {
+            shards: (0..shards).map(|_| Shard::new()).collect(),
+            hasher: RandomState::new(),
+        }
+    }
+    /// Inserts an entry with the given number of floating-point seconds
+    ///
+    /// Returns the previous value for the key if it had not timed out.
+    /// If the key's shard is full, its timed-out entries may be removed first.
+    pub fn insert<D: ToDuration>(&self, key: K, value: V, time: D) -> Option<V> {
+        let shard = self.shard(&key);
+        let mut map = shard.write();
+        // Scanning a shard blocks every other thread using it, so a full shard is only
+        // scanned once it has had at least half as many insertions as entries since its last scan.
+        // Otherwise it grows, which spreads the cost of each scan over the insertions before it.
+        let inserts = shard.inserts.fetch_add(1, Ordering::Relaxed);
+        if map.len() == map.capacity() && inserts >= map.len() / 2 && !map.contains_key(&key) {
+            shard.clean(&mut map);
+        }
+        match map.insert(key, (Timer::set(time), value)) {
+            Some((timer, value)) if !timer.is_ready() => Some(value),
+            _ => None,
+        }
+    }
+    /// Gets a clone of the value for the key
+    ///
+    /// Returns `None` if the entry has timed out or is not in the map.
+    pub fn get<Q>(&self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+        V: Clone,
+    {
+        self.get_with(key, V::clone)
+    }
+    /// Runs a function with a reference to the value for the key
+    ///
+    /// Returns `None` if the entry has timed out or is not in the map.
+    pub fn get_with<Q, F, R>(&self, key: &Q, f: F) -> Option<R>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+        F: FnOnce(&V) -> R,
+    {
+        let shard = self.read(key);
+        match shard.get(key) {
+            Some((timer, value)) if !timer.is_ready() => Some(f(value)),
+            _ => None,
+        }
+    }
+    /// Checks if the map contains an entry for the key that has not timed out
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.remaining(key).is_some()
+    }
+    /// Gets the time left for the entry with the key
+    ///
+    /// Returns `None` if the entry has timed out or is not in the map.
+    pub fn remaining<Q>(&self, key: &Q) -> Option<Duration>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.read(key)
+            .get(key)
+            .and_then(|(timer, _)| timer.duration_left())
+    }
+    /// Restarts the countdown of the entry with the key from its original [`Duration`]
+    ///
+    /// Returns `false` if the entry has timed out or is not in the map.
+    pub fn touch<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        match self.write(key).get_mut(key) {
+            Some((timer, _)) if !timer.is_ready() => {
+                timer.reset();
+                true
+            }
+            _ => false,
+        }
+    }
+    /// Removes the entry with the key and returns its value
+    ///
+    /// Returns `None` if the entry has timed out or is not in the map.
+    pub fn remove<Q>(&self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        match self.write(key).remove(key) {
+            Some((timer, value)) if !timer.is_ready() => Some(value),
+            _ => None,
+        }
+    }
+    /// Forces the removal of all entries whose [`Duration`] has elpased.
+    ///
+    /// Each shard is locked in turn, so only one shard is blocked at a time.
+    pub fn clean(&self) {
+        for shard in &self.shards {
+            shard.clean(&mut shard.write());
+        }
+    }
+    /// Removes all entries from the map
+    pub fn clear(&self) {
+        for shard in &self.shards {
+            shard.write().clear();
+        }
+    }
+    /// Gets the number of entries in the map that have not timed out.
+    ///
+    /// Since shards are counted one at a time, the result may be
+    /// out of date if other threads are modifying the map.
+    pub fn len(&self) -> usize {
+        self.shards
+            .iter()
+            .map(|shard| {
+                shard
+                    .map
+                    .read()
+                    .unwrap_or_else(PoisonError::into_inner)
+                    .values()
+                    .filter(|(timer, _)| !timer.is_ready())
+                    .count()
+            })
+            .sum()
+    }
+    /// Check if the map is empty or if all existing entries have timed out.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+    /// Gets the shard that the key belongs to
+    fn shard<Q>(&self, key: &Q) -> &Shard<K, V>
+    where
+        Q: Hash + ?Sized,
+    {
+        let hash = self.hasher.hash_one(key);
+        &self.shards[(hash % self.shards.len() as u64) as usize]
+    }
+    fn read<Q>(&self, key: &Q) -> RwLockReadGuard<'_, HashMap<K, (Timer, V)>>
+    where
+        Q: Hash + ?Sized,
+    {
+        self.shard(key)
+            .map
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+    fn write<Q>(&self, key: &Q) -> RwLockWriteGuard<'_, HashMap<K, (Timer, V)>>
+    where
+        Q: Hash + ?Sized,
+    {
+        self.shard(key).write()
+    }
+}
+
+impl<K, V> Default for ConcurrentTimedMap<K, V>
+where
+    K: Hash + Eq,
+{
+    fn default() -> Self {
+        ConcurrentTimedMap::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn insert_cleans_full_shards() {
+        let map = ConcurrentTimedMap::with_shards(1);
+        for i in 0..10_000 {
+            map.insert(i, (), 0.0);
+        }
+        assert!(map.shards[0].write().capacity() < 100);
+        assert!(map.is_empty());
+    }
+}
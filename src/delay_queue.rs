@@ -0,0 +1,144 @@
+use std::{
+    collections::VecDeque,
+    pin::Pin,
+    sync::{Arc, Mutex, MutexGuard, PoisonError},
+    task::{Context, Poll, Waker},
+};
+
+use crate::{waker::DeadlineTimer, CleanPolicy, Key, ListOrder, TimedList, ToDuration};
+
+struct State<T> {
+    list: TimedList<T>,
+    ready: VecDeque<T>,
+    waker: Option<Waker>,
+    /// Wakes the stream when the next element times out
+    timer: DeadlineTimer,
+}
+
+struct Shared<T> {
+    state: Mutex<State<T>>,
+}
+
+impl<T> Shared<T> {
+    fn lock(&self) -> MutexGuard<'_, State<T>> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+    fn insert(&self, element: T, time: std::time::Duration) -> Key {
+        let mut state = self.lock();
+        let key = state
+            .list
+            .insert(element, time)
+            .expect("DelayQueue lists are never bounded");
+        if let Some(waker) = state.waker.take() {
+            waker.wake();
+        }
+        key
+    }
+}
+
+/**
+An async queue that yields its elements as they time out
+
+[`DelayQueue`] implements `futures_core::Stream`, yielding each element once its [`Duration`](std::time::Duration)
+has elapsed, in the order they time out. The stream never ends on its own.
+Elements can be inserted from other tasks or threads through a [`DelayQueueHandle`].
+
+This does not depend on any particular async runtime. With the `async-io` or `async-std` feature,
+it waits on that crate's timers instead of the crate's shared background thread.
+*/
+pub struct DelayQueue<T> {
+    shared: Arc<Shared<T>>,
+}
+
+/// A handle for inserting elements into and removing elements from a [`DelayQueue`]
+///
+/// Handles can be cloned and sent to other tasks or threads.
+pub struct DelayQueueHandle<T> {
+    shared: Arc<Shared<T>>,
+}
+
+impl<T> DelayQueue<T> {
+    /// Creates a new empty [`DelayQueue`]
+    pub fn new() -> DelayQueue<T> {
+        let mut list = TimedList::with_order(ListOrder::Expiration);
+        list.set_clean_policy(CleanPolicy::Never);
+        DelayQueue {
+            shared: Arc::new(Shared {
+                state: Mutex::new(State {
+                    list,
+                    ready: VecDeque::new(),
+                    waker: None,
+                    timer: DeadlineTimer::default(),
+                }),
+            }),
+        }
+    }
+    /// Gets a [`DelayQueueHandle`] for inserting elements from elsewhere
+    pub fn handle(&self) -> DelayQueueHandle<T> {
+        DelayQueueHandle {
+            shared: Arc::clone(&self.shared),
+        }
+    }
+    /// Inserts an element that will be yielded after the given number of floating-point seconds
+    pub fn insert<D: ToDuration>(&self, element: T, time: D) -> Key {
+        self.shared.insert(element, time.to_duration())
+    }
+    /// Removes the element with the given [`Key`] if it has not timed out yet
+    pub fn remove(&self, key: Key) -> Option<T> {
+        self.shared.lock().list.remove(key)
+    }
+    /// Gets the number of elements that have not been yielded yet
+    pub fn len(&self) -> usize {
+        let state = self.shared.lock();
+        state.list.deadlines.len() + state.ready.len()
+    }
+    /// Checks if there are no elements left to yield
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<T> DelayQueueHandle<T> {
+    /// Inserts an element that will be yielded after the given number of floating-point seconds
+    pub fn insert<D: ToDuration>(&self, element: T, time: D) -> Key {
+        self.shared.insert(element, time.to_duration())
+    }
+    /// Removes the element with the given [`Key`] if it has not timed out yet
+    pub fn remove(&self, key: Key) -> Option<T> {
+        self.shared.lock().list.remove(key)
+    }
+}
+
+impl<T> Clone for DelayQueueHandle<T> {
+    fn clone(&self) -> Self {
+        DelayQueueHandle {
+            shared: Arc::clone(&self.shared),
+        }
+    }
+}
+
+impl<T> Default for DelayQueue<T> {
+    fn default() -> Self {
+        DelayQueue::new()
+    }
+}
+
+impl<T> futures_core::Stream for DelayQueue<T> {
+    type Item = T;
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<T>> {
+        let mut state = self.shared.lock();
+        if state.ready.is_empty() {
+            let expired = state.list.take_expired();
+            state.ready.extend(expired);
+        }
+        if let Some(elem) = state.ready.pop_front() {
+            return Poll::Ready(Some(elem));
+        }
+        state.waker = Some(cx.waker().clone());
+        match state.list.next_expiration() {
+            Some(deadline) => state.timer.poll_until(deadline, cx),
+            None => state.timer.clear(),
+        }
+        Poll::Pending
+    }
+}
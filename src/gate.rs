@@ -0,0 +1,268 @@
+use std::{
+    collections::VecDeque,
+    convert::TryFrom,
+    sync::atomic::{AtomicU64, Ordering},
+    time::{Duration, Instant},
+};
+
+use crate::ToDuration;
+
+/**
+A gate that lets something through at most once per interval
+
+This is useful for things like printing a warning at most once per minute.
+The first call to [`Gate::try_pass`] always passes.
+
+Use [`SyncGate`] to share a gate between threads.
+
+# Example
+```
+use eggtimer::Gate;
+
+let mut gate = Gate::new(60.0);
+assert!(gate.try_pass());
+assert!(!gate.try_pass());
+```
+*/
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Gate {
+    interval: Duration,
+    last: Option<Instant>,
+}
+
+impl Gate {
+    /// Creates a new [`Gate`] that passes at most once per the given number of floating-point seconds
+    pub fn new<D: ToDuration>(interval: D) -> Gate {
+        Gate {
+            interval: interval.to_duration(),
+            last: None,
+        }
+    }
+    /// Gets the interval
+    pub fn interval(&self) -> Duration {
+        self.interval
+    }
+    /// Tries to pass through the gate
+    ///
+    /// Returns `true` if at least the interval has passed since the last time this returned `true`.
+    pub fn try_pass(&mut self) -> bool {
+        let now = Instant::now();
+        match self.last {
+            Some(last) if now.duration_since(last) < self.interval => false,
+            _ => {
+                self.last = Some(now);
+                true
+            }
+        }
+    }
+    /// Resets the gate so that the next call to [`Gate::try_pass`] passes
+    pub fn reset(&mut self) {
+        self.last = None;
+    }
+}
+
+/**
+A thread-safe version of [`Gate`]
+
+[`SyncGate::try_pass`] only takes `&self`, so a [`SyncGate`] can be put in a `static`
+or shared behind an `Arc`. Exactly one of any number of racing callers passes.
+*/
+#[derive(Debug)]
+pub struct SyncGate {
+    start: Instant,
+    interval: u64,
+    next: AtomicU64,
+}
+
+impl SyncGate {
+    /// Creates a new [`SyncGate`] that passes at most once per the given number of floating-point seconds
+    pub fn new<D: ToDuration>(interval: D) -> SyncGate {
+        SyncGate {
+            start: Instant::now(),
+            interval: nanos(interval.to_duration()),
+            next: AtomicU64::new(0),
+        }
+    }
+    /// Gets the interval
+    pub fn interval(&self) -> Duration {
+        Duration::from_nanos(self.interval)
+    }
+    /// Tries to pass through the gate
+    ///
+    /// Returns `true` if at least the interval has passed since the last time this returned `true`.
+    pub fn try_pass(&self) -> bool {
+        let now = nanos(self.start.elapsed());
+        let next = self.next.load(Ordering::Relaxed);
+        now >= next
+            && self
+                .next
+                .compare_exchange(
+                    next,
+                    now.saturating_add(self.interval).max(1),
+                    Ordering::Relaxed,
+                    Ordering::Relaxed,
+                )
+                .is_ok()
+    }
+    /// Resets the gate so that the next call to [`SyncGate::try_pass`] passes
+    pub fn reset(&self) {
+        self.next.store(0, Ordering::Relaxed);
+    }
+}
+
+/**
+A gate that lets up to some number of things through per trailing window
+
+This is useful for protecting against log spam. Each pass reports how many
+attempts were suppressed since the previous pass.
+
+# Example
+```
+use eggtimer::BurstGate;
+
+let mut gate = BurstGate::new(2, 60.0);
+assert_eq!(gate.try_pass(), Some(0));
+assert_eq!(gate.try_pass(), Some(0));
+assert_eq!(gate.try_pass(), None);
+assert_eq!(gate.suppressed(), 1);
+```
+*/
+#[derive(Debug, Clone, PartialEq, PartialOrd)]
+pub struct BurstGate {
+    limit: usize,
+    window: Duration,
+    passes: VecDeque<Instant>,
+    suppressed: u64,
+}
+
+impl BurstGate {
+    /// Creates a new [`BurstGate`] that passes at most `n` times per trailing window
+    /// of the given number of floating-point seconds
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is 0
+    pub fn new<D: ToDuration>(n: usize, window: D) -> BurstGate {
+        assert!(n > 0, "BurstGate must allow at least 1 pass per window");
+        BurstGate {
+            limit: n,
+            window: window.to_duration(),
+            passes: VecDeque::with_capacity(n),
+            suppressed: 0,
+        }
+    }
+    /// Gets the maximum number of passes per window
+    pub fn limit(&self) -> usize {
+        self.limit
+    }
+    /// Gets the window
+    pub fn window(&self) -> Duration {
+        self.window
+    }
+    /// Tries to pass through the gate
+    ///
+    /// If fewer than the limit have passed within the trailing window, this returns the number of
+    /// attempts that were suppressed since the last pass. Otherwise, the attempt is suppressed and
+    /// this returns `None`.
+    pub fn try_pass(&mut self) -> Option<u64> {
+        let now = Instant::now();
+        while let Some(&first) = self.passes.front() {
+            if now.duration_since(first) >= self.window {
+                self.passes.pop_front();
+            } else {
+                break;
+            }
+        }
+        if self.passes.len() < self.limit {
+            self.passes.push_back(now);
+            Some(std::mem::replace(&mut self.suppressed, 0))
+        } else {
+            self.suppressed += 1;
+            None
+        }
+    }
+    /// Gets the number of attempts suppressed since the last pass
+    pub fn suppressed(&self) -> u64 {
+        self.suppressed
+    }
+    /// Resets the gate, forgetting all passes and suppressed attempts
+    pub fn reset(&mut self) {
+        self.passes.clear();
+        self.suppressed = 0;
+    }
+}
+
+/**
+A sampler that says yes roughly once per period
+
+[`Sampler::should_sample`] is cheap enough to call in tight loops. Rather than reading the
+clock on every call, it learns how many calls happen per period and only reads the clock
+about 16 times per period. The first call always samples.
+
+# Example
+```
+use eggtimer::Sampler;
+
+let mut sampler = Sampler::every(1.0);
+for frame in 0..1000 {
+    if sampler.should_sample() {
+        println!("frame {}", frame);
+    }
+}
+```
+*/
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Sampler {
+    period: Duration,
+    next: Option<Instant>,
+    calls: u64,
+    stride: u64,
+    countdown: u64,
+}
+
+impl Sampler {
+    /// Creates a new [`Sampler`] that samples about once per the given number of floating-point seconds
+    pub fn every<D: ToDuration>(period: D) -> Sampler {
+        Sampler {
+            period: period.to_duration(),
+            next: None,
+            calls: 0,
+            stride: 1,
+            countdown: 0,
+        }
+    }
+    /// Gets the period
+    pub fn period(&self) -> Duration {
+        self.period
+    }
+    /// Checks whether a sample should be taken now
+    #[inline]
+    pub fn should_sample(&mut self) -> bool {
+        self.calls += 1;
+        if self.countdown > 0 {
+            self.countdown -= 1;
+            return false;
+        }
+        self.check()
+    }
+    fn check(&mut self) -> bool {
+        let now = Instant::now();
+        match self.next {
+            Some(next) if now < next => {
+                self.countdown = self.stride - 1;
+                false
+            }
+            _ => {
+                self.next = Some(now + self.period);
+                self.stride = (self.calls / 16).max(1);
+                self.countdown = self.stride - 1;
+                self.calls = 0;
+                true
+            }
+        }
+    }
+}
+
+fn nanos(dur: Duration) -> u64 {
+    u64::try_from(dur.as_nanos()).unwrap_or(u64::MAX)
+}
@@ -0,0 +1,210 @@
+use std::{
+    collections::BTreeMap,
+    fmt,
+    sync::{Arc, Mutex, MutexGuard, PoisonError},
+    time::Duration,
+};
+
+use crate::{Timer, ToDuration};
+
+type Callback = Box<dyn FnMut(&Overdue) + Send>;
+
+/// An identifier for a component registered with a [`HeartbeatMonitor`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct HeartbeatId(u64);
+
+struct Component {
+    name: String,
+    timer: Timer,
+    /// Whether the callback has been called since the last beat
+    reported: bool,
+}
+
+struct Inner {
+    components: Mutex<(BTreeMap<u64, Component>, u64)>,
+    callback: Mutex<Option<Callback>>,
+}
+
+/// A component of a [`HeartbeatMonitor`] that has not beaten within its interval
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Overdue {
+    id: HeartbeatId,
+    name: String,
+    by: Duration,
+}
+
+impl Overdue {
+    /// Gets the component's [`HeartbeatId`]
+    pub fn id(&self) -> HeartbeatId {
+        self.id
+    }
+    /// Gets the name the component was registered with
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+    /// Gets how long past its interval the component is
+    pub fn by(&self) -> Duration {
+        self.by
+    }
+}
+
+/**
+Supervises components that are expected to beat at regular intervals
+
+Each component is registered with the interval it is expected to beat within,
+and calls [`HeartbeatMonitor::beat`] to show that it is still alive.
+A component that goes longer than its interval without beating is overdue.
+Clones share the same components, so one can be handed to each thread.
+
+An optional callback is called from [`HeartbeatMonitor::check`] once each time a component
+becomes overdue. To check regularly, call [`HeartbeatMonitor::check`] from a
+[`Scheduler`](crate::Scheduler) job.
+
+# Example
+```
+use eggtimer::HeartbeatMonitor;
+
+let monitor = HeartbeatMonitor::new().with_callback(|overdue| {
+    eprintln!("{} is {:?} overdue", overdue.name(), overdue.by());
+});
+let worker = monitor.register("worker", 5.0);
+monitor.beat(worker);
+assert!(monitor.check().is_empty());
+```
+*/
+#[derive(Clone)]
+pub struct HeartbeatMonitor {
+    inner: Arc<Inner>,
+}
+
+impl HeartbeatMonitor {
+    /// Creates a new [`HeartbeatMonitor`] with no components
+    pub fn new() -> HeartbeatMonitor {
+        HeartbeatMonitor {
+            inner: Arc::new(Inner {
+                components: Mutex::new((BTreeMap::new(), 0)),
+                callback: Mutex::new(None),
+            }),
+        }
+    }
+    /// Sets a function to call each time a component becomes overdue
+    ///
+    /// This replaces any previous callback, including for clones.
+    pub fn with_callback<F>(self, callback: F) -> Self
+    where
+        F: FnMut(&Overdue) + Send + 'static,
+    {
+        *lock(&self.inner.callback) = Some(Box::new(callback));
+        self
+    }
+    /// Registers a component that must beat every given number of floating-point seconds
+    ///
+    /// The component's first interval starts now.
+    pub fn register<N, D>(&self, name: N, interval: D) -> HeartbeatId
+    where
+        N: Into<String>,
+        D: ToDuration,
+    {
+        let mut components = lock(&self.inner.components);
+        let id = components.1;
+        components.1 += 1;
+        components.0.insert(
+            id,
+            Component {
+                name: name.into(),
+                timer: Timer::set(interval),
+                reported: false,
+            },
+        );
+        HeartbeatId(id)
+    }
+    /// Stops supervising a component
+    ///
+    /// Returns `false` if the component was not registered.
+    pub fn unregister(&self, id: HeartbeatId) -> bool {
+        lock(&self.inner.components).0.remove(&id.0).is_some()
+    }
+    /// Records a beat from a component, starting its next interval
+    ///
+    /// Returns `false` if the component is not registered.
+    pub fn beat(&self, id: HeartbeatId) -> bool {
+        match lock(&self.inner.components).0.get_mut(&id.0) {
+            Some(component) => {
+                component.timer.reset();
+                component.reported = false;
+                true
+            }
+            None => false,
+        }
+    }
+    /// Gets the number of registered components
+    pub fn len(&self) -> usize {
+        lock(&self.inner.components).0.len()
+    }
+    /// Checks if there are no registered components
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+    /// Gets every overdue component without calling the callback
+    pub fn overdue(&self) -> Vec<Overdue> {
+        lock(&self.inner.components)
+            .0
+            .iter()
+            .filter_map(|(&id, component)| overdue(id, component))
+            .collect()
+    }
+    /// Gets every overdue component, calling the callback for those that
+    /// have become overdue since the last check
+    pub fn check(&self) -> Vec<Overdue> {
+        let mut newly_overdue = Vec::new();
+        let all_overdue: Vec<Overdue> = lock(&self.inner.components)
+            .0
+            .iter_mut()
+            .filter_map(|(&id, component)| {
+                let overdue = overdue(id, component)?;
+                if !component.reported {
+                    component.reported = true;
+                    newly_overdue.push(overdue.clone());
+                }
+                Some(overdue)
+            })
+            .collect();
+        if let Some(callback) = &mut *lock(&self.inner.callback) {
+            for overdue in &newly_overdue {
+                callback(overdue);
+            }
+        }
+        all_overdue
+    }
+}
+
+impl Default for HeartbeatMonitor {
+    fn default() -> Self {
+        HeartbeatMonitor::new()
+    }
+}
+
+impl fmt::Debug for HeartbeatMonitor {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("HeartbeatMonitor")
+            .field("components", &self.len())
+            .finish()
+    }
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+fn overdue(id: u64, component: &Component) -> Option<Overdue> {
+    let left = component.timer.signed_left();
+    if left.is_negative() {
+        Some(Overdue {
+            id: HeartbeatId(id),
+            name: component.name.clone(),
+            by: left.abs(),
+        })
+    } else {
+        None
+    }
+}
@@ -0,0 +1,110 @@
+use std::time::Duration;
+
+use crate::ToDuration;
+
+const UNITS: [(&str, u64); 5] = [
+    ("year", 365 * 24 * 60 * 60),
+    ("day", 24 * 60 * 60),
+    ("hour", 60 * 60),
+    ("minute", 60),
+    ("second", 1),
+];
+
+/**
+Renders durations as human-readable relative times like "in 3 minutes" or "2 hours ago"
+
+By default, only the largest unit is shown, and anything under a second is "just now".
+
+# Example
+```
+use std::time::Duration;
+use eggtimer::Humanizer;
+
+let humanizer = Humanizer::new();
+assert_eq!(humanizer.remaining(Duration::from_secs(200)), "in 3 minutes");
+assert_eq!(humanizer.elapsed(Duration::from_secs(7300)), "2 hours ago");
+assert_eq!(humanizer.elapsed(Duration::from_millis(400)), "just now");
+
+let precise = Humanizer::new().with_max_units(2);
+assert_eq!(precise.remaining(Duration::from_secs(3900)), "in 1 hour and 5 minutes");
+```
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Humanizer {
+    just_now: Duration,
+    max_units: usize,
+}
+
+impl Default for Humanizer {
+    fn default() -> Self {
+        Humanizer {
+            just_now: Duration::from_secs(1),
+            max_units: 1,
+        }
+    }
+}
+
+impl Humanizer {
+    /// Creates a new [`Humanizer`] with the default settings
+    pub fn new() -> Humanizer {
+        Humanizer::default()
+    }
+    /// Sets how short a duration must be to be rendered as "just now"
+    pub fn with_just_now<D: ToDuration>(mut self, threshold: D) -> Humanizer {
+        self.just_now = threshold.to_duration();
+        self
+    }
+    /// Sets the maximum number of units to show, from largest to smallest
+    ///
+    /// A value of 0 is treated as 1.
+    pub fn with_max_units(mut self, max_units: usize) -> Humanizer {
+        self.max_units = max_units.max(1);
+        self
+    }
+    /// Renders a remaining time, like "in 3 minutes"
+    pub fn remaining(&self, duration: Duration) -> String {
+        self.render(duration)
+            .map(|amount| format!("in {}", amount))
+            .unwrap_or_else(|| "just now".into())
+    }
+    /// Renders an elapsed time, like "3 minutes ago"
+    pub fn elapsed(&self, duration: Duration) -> String {
+        self.render(duration)
+            .map(|amount| format!("{} ago", amount))
+            .unwrap_or_else(|| "just now".into())
+    }
+    /// Renders the amount of time, or `None` if it is "just now"
+    fn render(&self, duration: Duration) -> Option<String> {
+        if duration < self.just_now || duration.as_secs() == 0 {
+            return None;
+        }
+        let mut secs = duration.as_secs();
+        let parts: Vec<String> = UNITS
+            .iter()
+            .filter_map(|&(name, size)| {
+                let count = secs / size;
+                secs %= size;
+                match count {
+                    0 => None,
+                    1 => Some(format!("1 {}", name)),
+                    _ => Some(format!("{} {}s", count, name)),
+                }
+            })
+            .take(self.max_units)
+            .collect();
+        Some(match parts.split_last() {
+            Some((last, rest)) if !rest.is_empty() => format!("{} and {}", rest.join(", "), last),
+            _ => parts.concat(),
+        })
+    }
+}
+
+/// Renders a remaining time, like "in 3 minutes", with the default [`Humanizer`]
+pub fn humanize_remaining(duration: Duration) -> String {
+    Humanizer::new().remaining(duration)
+}
+
+/// Renders an elapsed time, like "3 minutes ago", with the default [`Humanizer`]
+pub fn humanize_elapsed(duration: Duration) -> String {
+    Humanizer::new().elapsed(duration)
+}
@@ -0,0 +1,186 @@
+use std::{
+    thread,
+    time::{Duration, Instant},
+};
+
+use crate::{Timer, ToDuration, TokenBucket};
+
+/// Extension methods for iterators that involve time
+pub trait IteratorExt: Iterator + Sized {
+    /// Creates an iterator that yields each item along with how long it took to produce
+    ///
+    /// # Example
+    /// ```
+    /// use eggtimer::IteratorExt;
+    ///
+    /// for (n, took) in (0..5u64).map(|n| (0..n * 1000).sum::<u64>()).timed() {
+    ///     println!("produced {} in {:?}", n, took);
+    /// }
+    /// ```
+    fn timed(self) -> Timed<Self> {
+        Timed { iter: self }
+    }
+    /// Creates an iterator that yields items until the given number of floating-point seconds has elapsed
+    ///
+    /// The time starts counting on the first call to [`Iterator::next`].
+    ///
+    /// # Example
+    /// ```
+    /// use eggtimer::IteratorExt;
+    ///
+    /// let mut tasks = vec![1, 2, 3, 4, 5];
+    /// // Do as many tasks as fit in 2 milliseconds
+    /// for task in tasks.drain(..).take_for(0.002) {
+    ///     println!("doing task {}", task);
+    /// }
+    /// ```
+    fn take_for<D: ToDuration>(self, time: D) -> TakeFor<Self> {
+        TakeFor {
+            iter: self,
+            time: time.to_duration(),
+            timer: None,
+        }
+    }
+    /// Creates an iterator that yields items no faster than once per the given number of floating-point seconds
+    ///
+    /// The first item is yielded immediately. Each later call to [`Iterator::next`]
+    /// gets the next item, then sleeps the current thread as needed before yielding it.
+    /// The end of the iterator is reported without sleeping.
+    fn throttled<D: ToDuration>(self, interval: D) -> Throttled<Self> {
+        Throttled {
+            iter: self,
+            interval: interval.to_duration(),
+            last: None,
+        }
+    }
+    /// Creates an iterator that yields items at a long-run rate of at most
+    /// `per_second` items per second, allowing bursts of up to `burst` items
+    ///
+    /// Each call to [`Iterator::next`] gets the next item, then sleeps the current thread as needed
+    /// before yielding it. The end of the iterator is reported without sleeping or using up allowance.
+    /// Unlike [`IteratorExt::throttled`], items that are requested slowly build up
+    /// allowance for a later burst. See [`TokenBucket`] for details.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `per_second` is not positive and finite or if `burst` is 0
+    fn rate_limited(self, per_second: f64, burst: u32) -> RateLimited<Self> {
+        RateLimited {
+            iter: self,
+            bucket: TokenBucket::new(per_second, burst),
+        }
+    }
+}
+
+impl<I> IteratorExt for I where I: Iterator {}
+
+/// An iterator that yields each item along with how long it took to produce
+///
+/// This is created by [`IteratorExt::timed`]
+#[derive(Debug, Clone)]
+pub struct Timed<I> {
+    iter: I,
+}
+
+impl<I> Iterator for Timed<I>
+where
+    I: Iterator,
+{
+    type Item = (I::Item, Duration);
+    fn next(&mut self) -> Option<Self::Item> {
+        let start = Instant::now();
+        let item = self.iter.next()?;
+        Some((item, start.elapsed()))
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+/// An iterator that yields items until a time budget has elapsed
+///
+/// This is created by [`IteratorExt::take_for`]
+#[derive(Debug, Clone)]
+pub struct TakeFor<I> {
+    iter: I,
+    time: Duration,
+    timer: Option<Timer>,
+}
+
+impl<I> Iterator for TakeFor<I>
+where
+    I: Iterator,
+{
+    type Item = I::Item;
+    fn next(&mut self) -> Option<Self::Item> {
+        let time = self.time;
+        if self
+            .timer
+            .get_or_insert_with(|| Timer::set(time))
+            .is_ready()
+        {
+            return None;
+        }
+        self.iter.next()
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.iter.size_hint().1)
+    }
+}
+
+/// An iterator that yields items no faster than once per interval
+///
+/// This is created by [`IteratorExt::throttled`]
+#[derive(Debug, Clone)]
+pub struct Throttled<I> {
+    iter: I,
+    interval: Duration,
+    last: Option<Instant>,
+}
+
+impl<I> Iterator for Throttled<I>
+where
+    I: Iterator,
+{
+    type Item = I::Item;
+    fn next(&mut self) -> Option<Self::Item> {
+        // Fetching first means the end of the iterator and slow items do not wait a full interval
+        let item = self.iter.next()?;
+        if let Some(last) = self.last {
+            let wait = (last + self.interval).saturating_duration_since(Instant::now());
+            if wait > Duration::from_secs(0) {
+                thread::sleep(wait);
+            }
+        }
+        self.last = Some(Instant::now());
+        Some(item)
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+/// An iterator that yields items no faster than a given rate, allowing bursts
+///
+/// This is created by [`IteratorExt::rate_limited`]
+#[derive(Debug, Clone)]
+pub struct RateLimited<I> {
+    iter: I,
+    bucket: TokenBucket,
+}
+
+impl<I> Iterator for RateLimited<I>
+where
+    I: Iterator,
+{
+    type Item = I::Item;
+    fn next(&mut self) -> Option<Self::Item> {
+        // Fetching first means the end of the iterator does not wait for or use up a token
+        let item = self.iter.next()?;
+        self.bucket.take();
+        Some(item)
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
@@ -0,0 +1,61 @@
+use std::time::Duration;
+
+use crate::Timer;
+
+/// A trait for values that can be linearly interpolated
+pub trait Lerp: Sized {
+    /// Interpolates between `self` at `t = 0` and `to` at `t = 1`
+    fn lerp(self, to: Self, t: f64) -> Self;
+}
+
+impl Lerp for f32 {
+    fn lerp(self, to: Self, t: f64) -> Self {
+        self + (to - self) * t as f32
+    }
+}
+
+impl Lerp for f64 {
+    fn lerp(self, to: Self, t: f64) -> Self {
+        self + (to - self) * t
+    }
+}
+
+impl Lerp for Duration {
+    fn lerp(self, to: Self, t: f64) -> Self {
+        Duration::from_secs_f64(self.as_secs_f64().lerp(to.as_secs_f64(), t).max(0.0))
+    }
+}
+
+/**
+Linearly interpolates between `a` at `t = 0` and `b` at `t = 1`
+
+`t` is not clamped, so values outside of 0 to 1 extrapolate.
+
+# Example
+```
+use eggtimer::lerp;
+
+assert_eq!(lerp(10.0, 20.0, 0.25), 12.5);
+```
+*/
+pub fn lerp<T: Lerp>(a: T, b: T, t: f64) -> T {
+    a.lerp(b, t)
+}
+
+impl Timer {
+    /**
+    Interpolates between `a` when the [`Timer`] starts and `b` when it ends
+
+    # Example
+    ```
+    use eggtimer::Timer;
+
+    let fade = Timer::set(2.0);
+    let alpha: f32 = fade.lerp_between(1.0, 0.0);
+    assert!(alpha <= 1.0 && alpha >= 0.0);
+    ```
+    */
+    pub fn lerp_between<T: Lerp>(&self, a: T, b: T) -> T {
+        a.lerp(b, self.progress())
+    }
+}
@@ -4,12 +4,61 @@
 //! - [`Elapsed`] is a timer that counts up and knows how much time has passed since it was started.
 //! - [`Timer`] is a timer that counts down from its set [`Duration`] and knows how much time it has left.
 //! - [`Stopwatch`] is a timer that counts up and can be paused and resumed.
+//! - [`Ticker`] ticks at a fixed rate, which can be given as a period or a [`Hertz`] frequency.
+//! - [`TokenBucket`] is a rate limiter that allows bursts.
+//! - [`Gate`] lets something through at most once per interval, and [`BurstGate`] up to some number of times per window.
+//!
+//! [`TimingStats`] keeps running statistics about measured times in constant memory.
+//! [`Reservoir`] keeps a fixed-size random sample of them for approximate percentiles,
+//! and [`Histogram`] counts them in buckets of bounded relative precision.
 //!
 //! In addition to the timer types, a collection type, [`TimedList`], is provided,
 //! which associates each element with a [`Duration`] and only retains elements whose [`Duration`] has not elapsed.
+//! [`TimedArray`] is a fixed-capacity version of [`TimedList`] that never allocates.
+//! [`TimedSet`] is a set whose elements have their [`Duration`] refreshed when they are inserted again.
+//! [`TimedQueue`] is a first-in-first-out queue whose elements time out.
+//! [`ConcurrentTimedMap`] is a map whose entries time out that can be shared between threads without a global lock.
+//! [`ExpiryNotifier`] cleans a [`TimedList`] on a background thread and sends timed-out elements over a channel.
+//! [`Scheduler`] runs jobs repeatedly on a background thread.
+//! [`Poller`] reports which of many [`Timer`]s have become ready each time it is polled, without a background thread.
+//! [`HeartbeatMonitor`] reports components that have stopped beating within their expected interval.
 //!
 //! By default, this crate us [`f32`] as the number type when dealing with seconds.
 //! You can enable the `f64` feature to use [`f64`] for seconds instead.
+//!
+//! Enabling the `async` feature adds [`sleep`] and [`DelayQueue`], lets [`Timer`]s be awaited, and lets
+//! [`Ticker`]s be turned into `futures_core::Stream`s, all without tying them to any particular async runtime.
+//!
+//! Enabling the `async-std` feature additionally lets [`Timer`]s and [`Ticker`]s be awaited
+//! with the `async-std` runtime's own timers, adds [`AsyncStdTicks`], and makes [`DelayQueue`] use those timers.
+//! Likewise, the `async-io` feature lets them be awaited with `async-io`'s timers and makes [`DelayQueue`]
+//! use those timers, for `smol` and other runtimes built on it. If both features are enabled,
+//! [`DelayQueue`] uses `async-io`'s timers, while [`AsyncStdTicks`] always uses `async-std`'s.
+//!
+//! Enabling the `chrono` feature implements [`ToDuration`] and [`FromDuration`] for `chrono::Duration`
+//! and allows a [`Timer`] to be set to end at a `chrono::DateTime`.
+//! Likewise, the `time` feature does the same for `time::Duration` and `time::OffsetDateTime`.
+//!
+//! Enabling the `crossbeam-channel` or `flume` feature allows timed-out elements and [`Timer`] notifications
+//! to be sent over those crates' channels. See [`NotifySender`].
+//!
+//! Enabling the `log` feature allows a [`ScopeTimer`] to report through the `log` crate.
+//!
+//! Enabling the `os-timers` feature makes the [`Scheduler`], [`ExpiryNotifier`], and the thread that wakes
+//! async timers wait on a `timerfd` instead of a condition variable. This feature only supports Linux.
+//! `kqueue` timers on BSD/macOS and waitable timers on Windows are not implemented, because they would need
+//! unsafe code, so on those platforms the feature is accepted but changes nothing.
+//!
+//! Enabling the `profiling` feature makes the [`profile!`] macro record timings in a global registry.
+//! Without it, the macro does nothing but evaluate its block.
+//!
+//! Enabling the `prometheus` feature allows [`TimingStats`], [`Histogram`], and [`TokenBucket`]
+//! to be rendered in the Prometheus text exposition format.
+//!
+//! Enabling the `rand` feature adds [`DurationRange`] and jittered [`Timer`]s for randomized timings.
+//!
+//! Enabling the `serde` feature implements `Serialize` and `Deserialize` for [`TimedList`],
+//! preserving the time each element has left.
 
 /// The type for a number of seconds
 #[cfg(not(feature = "f64"))]
@@ -18,7 +67,110 @@ pub type Seconds = f32;
 #[cfg(feature = "f64")]
 pub type Seconds = f64;
 
-use std::time::{Duration, Instant};
+mod alarm;
+mod array;
+#[cfg(feature = "async-io")]
+mod async_io_support;
+#[cfg(feature = "async-std")]
+mod async_std_support;
+mod bucket;
+mod cancel;
+mod concurrent;
+#[cfg(feature = "async")]
+mod delay_queue;
+mod gate;
+mod heartbeat;
+mod humanize;
+mod iter;
+mod lerp;
+mod macros;
+mod measure;
+mod notifier;
+mod poller;
+mod profile;
+#[cfg(feature = "prometheus")]
+mod prometheus;
+mod queue;
+#[cfg(feature = "rand")]
+mod random;
+mod scheduler;
+mod scope;
+#[cfg(feature = "serde")]
+mod serde_impls;
+mod set;
+mod shared;
+mod signed;
+mod spin;
+mod stats;
+mod ticker;
+mod timed_fn;
+mod timestamp;
+mod units;
+mod wait;
+#[cfg(feature = "async")]
+mod waker;
+
+pub use array::TimedArray;
+#[cfg(feature = "async-std")]
+pub use async_std_support::AsyncStdTicks;
+pub use bucket::TokenBucket;
+pub use cancel::CancelToken;
+pub use concurrent::ConcurrentTimedMap;
+#[cfg(feature = "async")]
+pub use delay_queue::{DelayQueue, DelayQueueHandle};
+pub use gate::{BurstGate, Gate, Sampler, SyncGate};
+pub use heartbeat::{HeartbeatId, HeartbeatMonitor, Overdue};
+pub use humanize::{humanize_elapsed, humanize_remaining, Humanizer};
+pub use iter::{IteratorExt, RateLimited, TakeFor, Throttled, Timed};
+pub use lerp::{lerp, Lerp};
+pub use measure::{
+    calibrate, compare, measure_calibrated, measure_n, measure_parallel, run_for, Comparison,
+    ParallelMeasurement,
+};
+pub use notifier::{ExpiryNotifier, NotifySender};
+pub use poller::{Poller, PollerId};
+pub use profile::{
+    profile_record, profile_report, profile_report_thread, profile_reset, ProfileEntry,
+    ProfileScope,
+};
+pub use queue::TimedQueue;
+#[cfg(feature = "rand")]
+pub use random::DurationRange;
+pub use scheduler::{JobHandle, Scheduler};
+pub use scope::ScopeTimer;
+pub use set::TimedSet;
+pub use shared::{
+    stopwatch, stopwatch_report, AtomicStopwatch, NamedStopwatch, SectionGuard, SharedTimer,
+};
+pub use signed::SignedDuration;
+pub use spin::SpinSleeper;
+pub use stats::{Histogram, Reservoir, TimingStats};
+#[cfg(feature = "async")]
+pub use ticker::Ticks;
+pub use ticker::{Hertz, Jitter, Metronome, Ticker, ToHertz};
+pub use timed_fn::{CallStats, TimedCall, TimedFn};
+pub use timestamp::Timestamp;
+pub use units::{
+    ceil_to, floor_to, hours, micros, millis, minutes, nanos, round_to, secs, FromDurationRounded,
+    Micros, Millis, Nanos, Rounding, SecsNanos, TimeUnit,
+};
+pub use wait::{
+    poll_until, retry, wait_while_with_timer, Backoff, RetryPolicy, Sleeper, TimedOut, Waker,
+};
+#[cfg(feature = "async")]
+pub use waker::{await_all, await_any, sleep, sleep_until, timeout, Sleep};
+
+use std::{
+    convert::TryFrom,
+    fmt,
+    iter::Sum,
+    ops::{Add, AddAssign, SubAssign},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, OnceLock,
+    },
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
 
 /// A trait for types that can be turned into a [`Duration`]
 pub trait ToDuration {
@@ -90,7 +242,127 @@ impl ToDuration for usize {
     }
 }
 
+/**
+A number of minutes and a number of seconds
+
+This reads naturally for countdowns.
+The result saturates if it does not fit in a [`Duration`].
+This is the only tuple that implements [`ToDuration`], so integer literals work without suffixes.
+For seconds and nanoseconds, use [`SecsNanos`].
+
+# Example
+```
+use eggtimer::Timer;
+
+let timer = Timer::set((2, 30));
+assert_eq!(timer.max_duration().as_secs(), 150);
+```
+*/
+impl ToDuration for (u64, u64) {
+    fn to_duration(&self) -> Duration {
+        Duration::from_secs(self.0.saturating_mul(60).saturating_add(self.1))
+    }
+}
+
+/// The time since the UNIX epoch
+///
+/// # Panics
+///
+/// Panics if the [`SystemTime`] is before the UNIX epoch
+impl ToDuration for SystemTime {
+    fn to_duration(&self) -> Duration {
+        self.duration_since(UNIX_EPOCH)
+            .expect("Attempted to convert SystemTime before the UNIX epoch to Duration")
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl ToDuration for chrono::Duration {
+    fn to_duration(&self) -> Duration {
+        self.to_std()
+            .expect("Attempted to convert negative chrono::Duration to Duration")
+    }
+}
+
+#[cfg(feature = "time")]
+impl ToDuration for time::Duration {
+    fn to_duration(&self) -> Duration {
+        Duration::try_from(*self).expect("Attempted to convert negative time::Duration to Duration")
+    }
+}
+
+/**
+A trait for types that can be turned into a [`Duration`], but might be negative
+
+This is implemented for the signed integer types, which are interpreted as whole seconds,
+and for the floating-point types.
+
+# Example
+```
+use eggtimer::TryToDuration;
+
+assert_eq!(30i32.try_to_duration().unwrap().as_secs(), 30);
+assert!((-1i64).try_to_duration().is_err());
+```
+*/
+pub trait TryToDuration {
+    /// Try to convert the value into the [`Duration`]
+    fn try_to_duration(&self) -> Result<Duration, NegativeDuration>;
+}
+
+/// An error indicating that a negative value could not be converted to a [`Duration`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct NegativeDuration;
+
+impl fmt::Display for NegativeDuration {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "attempted to convert a negative value to a Duration")
+    }
+}
+
+impl std::error::Error for NegativeDuration {}
+
+macro_rules! try_to_duration_signed {
+    ($($ty:ty),*) => {
+        $(
+            impl TryToDuration for $ty {
+                fn try_to_duration(&self) -> Result<Duration, NegativeDuration> {
+                    if *self < 0 {
+                        Err(NegativeDuration)
+                    } else {
+                        Ok(Duration::from_secs(u64::try_from(*self).unwrap_or(u64::MAX)))
+                    }
+                }
+            }
+        )*
+    };
+}
+
+try_to_duration_signed!(i8, i16, i32, i64, i128, isize);
+
+impl TryToDuration for f32 {
+    fn try_to_duration(&self) -> Result<Duration, NegativeDuration> {
+        if *self < 0.0 {
+            Err(NegativeDuration)
+        } else {
+            Ok(self.to_duration())
+        }
+    }
+}
+
+impl TryToDuration for f64 {
+    fn try_to_duration(&self) -> Result<Duration, NegativeDuration> {
+        if *self < 0.0 {
+            Err(NegativeDuration)
+        } else {
+            Ok(self.to_duration())
+        }
+    }
+}
+
 /// A trait for types that can be created from a [`Duration`]
+///
+/// Integers are whole seconds. Use [`Millis`], [`Micros`], or [`Nanos`] for finer units.
 pub trait FromDuration {
     /// Create the value from a [`Duration`]
     fn from_duration(duration: Duration) -> Self;
@@ -132,6 +404,57 @@ impl FromDuration for f64 {
     }
 }
 
+/// The [`SystemTime`] the [`Duration`] after the UNIX epoch
+///
+/// # Panics
+///
+/// Panics if the result cannot be represented by the platform's [`SystemTime`]
+impl FromDuration for SystemTime {
+    fn from_duration(duration: Duration) -> Self {
+        UNIX_EPOCH
+            .checked_add(duration)
+            .expect("Attempted to convert Duration too large for SystemTime")
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl FromDuration for chrono::Duration {
+    fn from_duration(duration: Duration) -> Self {
+        chrono::Duration::from_std(duration).unwrap_or(chrono::Duration::MAX)
+    }
+}
+
+#[cfg(feature = "time")]
+impl FromDuration for time::Duration {
+    fn from_duration(duration: Duration) -> Self {
+        time::Duration::try_from(duration).unwrap_or(time::Duration::MAX)
+    }
+}
+
+/**
+Checks if two durations are within `epsilon` of each other
+
+This is useful for asserting on timings in tests.
+Any [`ToDuration`] can be used, including floating-point seconds.
+
+# Example
+```
+use std::time::Duration;
+use eggtimer::approx_eq;
+
+assert!(approx_eq(1.0, Duration::from_millis(1005), 0.01));
+assert!(!approx_eq(1.0, 1.1, 0.01));
+```
+*/
+pub fn approx_eq<A, B, E>(a: A, b: B, epsilon: E) -> bool
+where
+    A: ToDuration,
+    B: ToDuration,
+    E: ToDuration,
+{
+    a.to_duration().abs_diff(b.to_duration()) <= epsilon.to_duration()
+}
+
 /// A simple timer that knows how long since it started
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Elapsed {
@@ -170,6 +493,10 @@ impl Default for Elapsed {
 }
 
 /// A timer that counts down and knows when a [`Duration`] has elapsed
+///
+/// The derived ordering compares the start [`Instant`] first and the set [`Duration`] second,
+/// which is rarely what you want when sorting timers.
+/// Use [`Timer::cmp_by_remaining`] or [`Timer::cmp_by_deadline`] instead.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Timer {
     elapsed: Elapsed,
@@ -184,6 +511,44 @@ impl Timer {
             duration: time.to_duration(),
         }
     }
+    /// Creates a new [`Timer`] from a value that might be negative
+    ///
+    /// Returns an error if the value is negative.
+    pub fn try_set<D: TryToDuration>(time: D) -> Result<Timer, NegativeDuration> {
+        time.try_to_duration().map(Timer::set)
+    }
+    /// Creates a new [`Timer`] that ends at the given [`Instant`]
+    ///
+    /// If the [`Instant`] has already passed, the [`Timer`] is immediately ready.
+    pub fn until(deadline: Instant) -> Timer {
+        let now = Instant::now();
+        Timer {
+            elapsed: Elapsed {
+                start: now.min(deadline),
+            },
+            duration: deadline.saturating_duration_since(now),
+        }
+    }
+    /// Creates a new [`Timer`] that ends at the given `chrono::DateTime`
+    ///
+    /// If the date-time has already passed, the [`Timer`] is immediately ready.
+    #[cfg(feature = "chrono")]
+    pub fn until_datetime<Tz: chrono::TimeZone>(datetime: chrono::DateTime<Tz>) -> Timer {
+        let time = datetime
+            .signed_duration_since(chrono::Utc::now())
+            .to_std()
+            .unwrap_or_else(|_| 0u64.to_duration());
+        Timer::set(time)
+    }
+    /// Creates a new [`Timer`] that ends at the given `time::OffsetDateTime`
+    ///
+    /// If the date-time has already passed, the [`Timer`] is immediately ready.
+    #[cfg(feature = "time")]
+    pub fn until_offset_datetime(datetime: time::OffsetDateTime) -> Timer {
+        let time = Duration::try_from(datetime - time::OffsetDateTime::now_utc())
+            .unwrap_or_else(|_| 0u64.to_duration());
+        Timer::set(time)
+    }
     /// Resets the [`Timer`]
     pub fn reset(&mut self) {
         self.elapsed = Elapsed::start();
@@ -192,6 +557,12 @@ impl Timer {
     pub fn duration_left(&self) -> Option<Duration> {
         self.duration.checked_sub(self.elapsed.duration())
     }
+    /// Gets the time left as a [`SignedDuration`]
+    ///
+    /// This is negative by how overdue the [`Timer`] is once it is ready.
+    pub fn signed_left(&self) -> SignedDuration {
+        SignedDuration::between(Instant::now(), self.ends_at())
+    }
     /// Gets the time left as a floating-point number of seconds
     pub fn seconds_left(&self) -> Seconds {
         Seconds::from_duration(self.duration) - self.elapsed.seconds()
@@ -229,10 +600,205 @@ impl Timer {
     pub fn started_at(&self) -> Instant {
         self.elapsed.started_at()
     }
+    /**
+    Gets a value that displays the elapsed time with the given number of decimal places in the given unit
+
+    # Example
+    ```
+    use eggtimer::{TimeUnit, Timer};
+
+    let timer = Timer::set(10.0);
+    println!("{}", timer.display_with(1, TimeUnit::Millis));
+    ```
+    */
+    pub fn display_with(&self, precision: usize, unit: TimeUnit) -> TimerDisplay {
+        TimerDisplay {
+            timer: *self,
+            precision,
+            unit,
+        }
+    }
+    /// Gets the fraction of the set [`Duration`] that has elapsed, between 0 and 1
+    ///
+    /// A [`Timer`] set with a zero [`Duration`] is always complete.
+    pub fn progress(&self) -> f64 {
+        if self.duration == Duration::from_secs(0) {
+            1.0
+        } else {
+            (self.duration().as_secs_f64() / self.duration.as_secs_f64()).min(1.0)
+        }
+    }
+    /**
+    Renders the [`Timer`]'s progress as a text progress bar like `[#####-----] 50%`
+
+    `width` is the number of characters between the brackets.
+
+    # Example
+    ```
+    use eggtimer::Timer;
+
+    let timer = Timer::set(60.0);
+    println!("{}", timer.render_bar(20));
+    ```
+    */
+    pub fn render_bar(&self, width: usize) -> String {
+        self.render_bar_with(width, '#', '-')
+    }
+    /// Renders the [`Timer`]'s progress as a text progress bar with the given characters for the filled and empty parts
+    ///
+    /// See [`Timer::render_bar`].
+    pub fn render_bar_with(&self, width: usize, filled: char, empty: char) -> String {
+        let progress = self.progress();
+        let filled_width = ((progress * width as f64) as usize).min(width);
+        let mut bar = String::with_capacity(width + 7);
+        bar.push('[');
+        bar.extend(std::iter::repeat_n(filled, filled_width));
+        bar.extend(std::iter::repeat_n(empty, width - filled_width));
+        bar.push_str(&format!("] {}%", (progress * 100.0) as u32));
+        bar
+    }
+    /// Gets the [`Instant`] the given number of floating-point seconds after the [`Timer`] was started
+    pub fn deadline_after<D: ToDuration>(&self, time: D) -> Instant {
+        self.started_at() + time.to_duration()
+    }
+    /// Checks if at least the given number of floating-point seconds has passed since the [`Timer`] was started
+    ///
+    /// This does not depend on the set [`Duration`].
+    pub fn has_elapsed<D: ToDuration>(&self, time: D) -> bool {
+        self.duration() >= time.to_duration()
+    }
+    /// Gets the time left until the given number of floating-point seconds has passed since the [`Timer`] was started
+    ///
+    /// Returns `None` if that much time has already passed.
+    pub fn remaining_until<D: ToDuration>(&self, time: D) -> Option<Duration> {
+        time.to_duration().checked_sub(self.duration())
+    }
+    /// Compares two [`Timer`]s by how much time they have left
+    ///
+    /// Ready [`Timer`]s have no time left and compare equal to each other.
+    pub fn cmp_by_remaining(&self, other: &Timer) -> std::cmp::Ordering {
+        self.duration_left().cmp(&other.duration_left())
+    }
+    /// Compares two [`Timer`]s by the [`Instant`] at which they end
+    pub fn cmp_by_deadline(&self, other: &Timer) -> std::cmp::Ordering {
+        self.ends_at().cmp(&other.ends_at())
+    }
+    /**
+    Gets the [`Timer`] with the least time left
+
+    Returns `None` if there are no [`Timer`]s.
+
+    # Example
+    ```
+    use eggtimer::Timer;
+
+    let timers = [Timer::set(5.0), Timer::set(1.0), Timer::set(3.0)];
+    let soonest = Timer::min_remaining(&timers).unwrap();
+    assert_eq!(soonest.max_seconds(), 1.0);
+    ```
+    */
+    pub fn min_remaining<'a, I>(timers: I) -> Option<&'a Timer>
+    where
+        I: IntoIterator<Item = &'a Timer>,
+    {
+        timers.into_iter().min_by(|a, b| a.cmp_by_deadline(b))
+    }
+    /// Gets the [`Timer`] with the most time left
+    ///
+    /// Returns `None` if there are no [`Timer`]s.
+    pub fn max_remaining<'a, I>(timers: I) -> Option<&'a Timer>
+    where
+        I: IntoIterator<Item = &'a Timer>,
+    {
+        timers.into_iter().max_by(|a, b| a.cmp_by_deadline(b))
+    }
+    /// Gets the wall-clock time at which the [`Timer`] was started
+    ///
+    /// This is best-effort. It is computed from the current wall-clock time, which may have been adjusted since.
+    pub fn started_at_system_time(&self) -> SystemTime {
+        SystemTime::now() - self.duration()
+    }
+    /// Gets the wall-clock time at which the [`Timer`] will or did end
+    ///
+    /// This is best-effort. It is computed from the current wall-clock time, which may be adjusted before then.
+    pub fn ends_at_system_time(&self) -> SystemTime {
+        let left = self.signed_left();
+        if left.is_negative() {
+            SystemTime::now() - left.abs()
+        } else {
+            SystemTime::now() + left.abs()
+        }
+    }
+    /**
+    Gets a [`std::sync::mpsc::Receiver`] that receives a message when the [`Timer`] is ready
+
+    This lets threads that wait on channels integrate countdowns without polling.
+    The message is sent by the global [`Scheduler`]'s thread.
+
+    # Example
+    ```
+    use eggtimer::Timer;
+
+    let timer = Timer::set(0.01);
+    timer.notify().recv().unwrap();
+    ```
+    */
+    pub fn notify(&self) -> std::sync::mpsc::Receiver<()> {
+        let (sender, receiver) = std::sync::mpsc::channel();
+        self.notify_with(sender);
+        receiver
+    }
+    /// Sends a message over the given [`NotifySender`] when the [`Timer`] is ready
+    ///
+    /// See [`Timer::notify`].
+    pub fn notify_with<S: NotifySender<()>>(&self, sender: S) {
+        Scheduler::global().at(self.ends_at(), move || {
+            sender.notify(());
+        });
+    }
+    /// Checks if the elapsed time is within `tolerance` of `expected`
+    pub fn elapsed_within<D: ToDuration, T: ToDuration>(&self, expected: D, tolerance: T) -> bool {
+        approx_eq(self.duration(), expected, tolerance)
+    }
     /// Gets the [`Instant`] at which the [`Timer`] will or did end
     pub fn ends_at(&self) -> Instant {
         self.elapsed.started_at() + self.duration
     }
+    /**
+    Carves out a sub-budget for the next of `n` sequential phases
+
+    The returned [`Timer`] starts now and gets an equal share of the time left.
+    Calling this at the start of each phase with the number of phases remaining
+    rolls any time left over from earlier phases into later ones.
+    If the [`Timer`] is ready, the sub-budget is empty. An `n` of 0 is treated as 1.
+
+    # Example
+    ```
+    use std::time::Duration;
+    use eggtimer::Timer;
+
+    let budget = Timer::set(9.0);
+    let first = budget.split(3);
+    assert!(first.max_duration() <= Duration::from_secs(3));
+    let rest = budget.take(0.5);
+    assert!(rest.max_duration() <= Duration::from_secs(9) / 2);
+    ```
+    */
+    pub fn split(&self, n: u32) -> Timer {
+        Timer::set(self.duration_left().unwrap_or_default() / n.max(1))
+    }
+    /// Carves out a sub-budget with the given fraction of the time left
+    ///
+    /// The returned [`Timer`] starts now. The fraction is clamped between 0 and 1,
+    /// so the sub-budget never ends after this [`Timer`]. If the [`Timer`] is ready, the sub-budget is empty.
+    pub fn take(&self, fraction: f64) -> Timer {
+        let fraction = if fraction.is_nan() {
+            0.0
+        } else {
+            fraction.clamp(0.0, 1.0)
+        };
+        Timer::set(self.duration_left().unwrap_or_default().mul_f64(fraction))
+    }
     /// If the timer is ready, runs the given function with the elapsed
     /// floating-point number of seconds as a parameter and resets the timer
     pub fn tick<F, R>(&mut self, mut f: F) -> Option<R>
@@ -252,6 +818,61 @@ impl Default for Timer {
     }
 }
 
+/// Shows the elapsed time in seconds, like `1.234s`
+///
+/// The precision defaults to 3 decimal places and can be changed in the format string, like `{:.1}`.
+/// Use [`Timer::display_with`] to show other units.
+impl fmt::Display for Timer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let precision = f.precision().unwrap_or(3);
+        self.display_with(precision, TimeUnit::Seconds).fmt(f)
+    }
+}
+
+/// Extends the time left
+impl AddAssign<Duration> for Timer {
+    fn add_assign(&mut self, time: Duration) {
+        self.duration += time;
+    }
+}
+
+/// Shortens the time left
+///
+/// The set [`Duration`] saturates at zero.
+impl SubAssign<Duration> for Timer {
+    fn sub_assign(&mut self, time: Duration) {
+        self.duration = self.duration.saturating_sub(time);
+    }
+}
+
+/// Gets the [`Instant`] the given [`Duration`] after the [`Timer`] ends
+impl Add<Duration> for Timer {
+    type Output = Instant;
+    fn add(self, time: Duration) -> Instant {
+        self.ends_at() + time
+    }
+}
+
+/// A displayable view of a [`Timer`]'s elapsed time, created with [`Timer::display_with`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct TimerDisplay {
+    timer: Timer,
+    precision: usize,
+    unit: TimeUnit,
+}
+
+impl fmt::Display for TimerDisplay {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{:.*}{}",
+            self.precision,
+            self.unit.count(self.timer.duration()),
+            self.unit.suffix()
+        )
+    }
+}
+
 /// A timer that can be paused and resumed.
 ///
 /// The reported elapsed times do not include periods when the timer was paused
@@ -316,60 +937,656 @@ impl Stopwatch {
             self.resume();
         }
     }
+    /// Adds the time elapsed on another [`Stopwatch`] to this one
+    ///
+    /// Whether this [`Stopwatch`] is paused does not change.
+    pub fn merge(&mut self, other: &Stopwatch) {
+        self.prev_dur += other.duration();
+    }
     /// Gets the [`Instant`] at which the [`Stopwatch`] was last resumed
     pub fn started_at(&self) -> Instant {
         self.last_start
     }
 }
 
+/// Adds to the elapsed time
+impl AddAssign<Duration> for Stopwatch {
+    fn add_assign(&mut self, time: Duration) {
+        self.prev_dur += time;
+    }
+}
+
+/// Subtracts from the elapsed time
+///
+/// The elapsed time saturates at zero.
+impl SubAssign<Duration> for Stopwatch {
+    fn sub_assign(&mut self, time: Duration) {
+        if !self.paused {
+            let now = Instant::now();
+            self.prev_dur += now.duration_since(self.last_start);
+            self.last_start = now;
+        }
+        self.prev_dur = self.prev_dur.saturating_sub(time);
+    }
+}
+
 impl Default for Stopwatch {
     fn default() -> Self {
         Stopwatch::start()
     }
 }
 
+/// Combines two [`Stopwatch`]es with [`Stopwatch::merge`]
+impl Add for Stopwatch {
+    type Output = Stopwatch;
+    fn add(mut self, other: Stopwatch) -> Stopwatch {
+        self.merge(&other);
+        self
+    }
+}
+
+/**
+Combines [`Stopwatch`]es into a paused [`Stopwatch`] with their total elapsed time
+
+# Example
+```
+use eggtimer::Stopwatch;
+
+let per_thread = vec![Stopwatch::start(), Stopwatch::start()];
+let total: Stopwatch = per_thread.into_iter().sum();
+println!("{:?}", total.duration());
+```
+*/
+impl Sum for Stopwatch {
+    fn sum<I: Iterator<Item = Stopwatch>>(iter: I) -> Self {
+        iter.fold(Stopwatch::start_paused(), Add::add)
+    }
+}
+
+/// Gets the total elapsed time of [`Stopwatch`]es
+impl Sum<Stopwatch> for Duration {
+    fn sum<I: Iterator<Item = Stopwatch>>(iter: I) -> Self {
+        iter.map(|stopwatch| stopwatch.duration()).sum()
+    }
+}
+
+/// Gets the total elapsed time of [`Stopwatch`]es
+impl<'a> Sum<&'a Stopwatch> for Duration {
+    fn sum<I: Iterator<Item = &'a Stopwatch>>(iter: I) -> Self {
+        iter.map(|stopwatch| stopwatch.duration()).sum()
+    }
+}
+
+/// A handle to an element inserted into a [`TimedList`]
+///
+/// It can be used to remove or adjust that specific element later.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Key(u64);
+
+#[derive(Debug, Clone, PartialEq, PartialOrd)]
+struct Entry<T> {
+    key: Key,
+    timer: Timer,
+    elem: T,
+}
+
+/// A callback for elements that have timed out
+struct OnExpire<T>(Arc<dyn Fn(T) + Send + Sync>);
+
+impl<T> Clone for OnExpire<T> {
+    fn clone(&self) -> Self {
+        OnExpire(Arc::clone(&self.0))
+    }
+}
+
+impl<T> fmt::Debug for OnExpire<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "OnExpire")
+    }
+}
+
+/// How many of a [`TimedList`]'s or [`TimedArray`]'s sorted deadlines are known to have passed
+///
+/// Since time only moves forward, this stays correct until a deadline is removed from the known part.
+#[derive(Debug, Default)]
+struct ExpiredHint(AtomicUsize);
+
+impl ExpiredHint {
+    /// Gets the number of the given sorted deadlines that have passed
+    ///
+    /// Only the deadlines after the ones already known to have passed are searched.
+    fn count(&self, deadlines: &[Instant]) -> usize {
+        let known = self.0.load(Ordering::Relaxed);
+        let now = Instant::now();
+        match deadlines.get(known) {
+            Some(deadline) if *deadline < now => {
+                let expired =
+                    known + deadlines[known..].partition_point(|deadline| *deadline < now);
+                self.0.store(expired, Ordering::Relaxed);
+                expired
+            }
+            _ => known,
+        }
+    }
+    /// Accounts for the deadline at the given index being removed
+    fn removed(&mut self, i: usize) {
+        let expired = self.0.get_mut();
+        if i < *expired {
+            *expired -= 1;
+        }
+    }
+    /// Forgets which deadlines have passed, for when they are rearranged
+    fn reset(&mut self) {
+        *self.0.get_mut() = 0;
+    }
+}
+
+impl Clone for ExpiredHint {
+    fn clone(&self) -> Self {
+        ExpiredHint(AtomicUsize::new(self.0.load(Ordering::Relaxed)))
+    }
+}
+
+/// The order in which a [`TimedList`] keeps its elements
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ListOrder {
+    /// Elements are kept in the order they were inserted
+    #[default]
+    Insertion,
+    /// Elements are kept in the order they will time out, soonest first
+    ///
+    /// Removing timed-out elements only needs to touch the elements
+    /// that have actually timed out, which is much faster for large lists.
+    Expiration,
+}
+
+/// What a bounded [`TimedList`] does when an element is inserted while it is full
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Eviction {
+    /// The element that will time out soonest is removed to make room
+    SoonestToExpire,
+    /// The element that was inserted first is removed to make room
+    OldestInserted,
+    /// The new element is not inserted
+    Reject,
+}
+
+/// When a [`TimedList`] implicitly removes timed-out elements
+///
+/// Timed-out elements are never iterated over regardless of the policy.
+/// The policy only controls when the memory they use is freed.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CleanPolicy {
+    /// Clean whenever the list is iterated over mutably
+    #[default]
+    MutableIteration,
+    /// Clean on every `n`th call to a mutable method that inserts, removes, adjusts, or iterates over elements
+    EveryN(usize),
+    /// Clean on calls to mutable methods when more than the given fraction of elements have timed out
+    ExpiredRatio(f64),
+    /// Only clean when [`TimedList::clean`] is called explicitly
+    Never,
+}
+
 /// An iterable list structure where each element has an associated [`Duration`].
 ///
 /// When an element's [`Duration`] has elapsed, the element is removed from the
-/// list upon the next mutable function call. Timed-out elements will never be iterated over.
-#[derive(Debug, Clone, PartialEq, PartialOrd, Default)]
+/// list upon a later mutable function call, as determined by the list's [`CleanPolicy`].
+/// Timed-out elements will never be iterated over.
+///
+/// Lists are compared by their elements that have not timed out and those elements' deadlines,
+/// in list order. Settings such as the [`ListOrder`], bound, and callback are not considered.
+#[derive(Debug, Clone, Default)]
 pub struct TimedList<T> {
-    list: Vec<(Timer, T)>,
+    order: ListOrder,
+    list: Vec<Entry<T>>,
+    deadlines: Vec<Instant>,
+    expired: ExpiredHint,
+    next_key: u64,
+    on_expire: Option<OnExpire<T>>,
+    bound: Option<(usize, Eviction)>,
+    clean_policy: CleanPolicy,
+    calls: usize,
 }
 
 impl<T> TimedList<T> {
     /// Creates a new [`TimedList`]
     pub fn new() -> TimedList<T> {
-        TimedList { list: Vec::new() }
+        TimedList::with_order(ListOrder::Insertion)
+    }
+    /// Creates a new [`TimedList`] that keeps its elements in the given [`ListOrder`]
+    pub fn with_order(order: ListOrder) -> TimedList<T> {
+        TimedList {
+            order,
+            list: Vec::new(),
+            deadlines: Vec::new(),
+            expired: ExpiredHint::default(),
+            next_key: 0,
+            on_expire: None,
+            bound: None,
+            clean_policy: CleanPolicy::MutableIteration,
+            calls: 0,
+        }
+    }
+    /// Creates a new [`TimedList`] that holds at most the given number of elements
+    ///
+    /// When an element is inserted while the list is full, timed-out elements are removed first.
+    /// If the list is still full, room is made according to the given [`Eviction`] policy.
+    pub fn bounded(capacity: usize, eviction: Eviction) -> TimedList<T> {
+        let mut list = TimedList::new();
+        list.set_bound(capacity, eviction);
+        list
+    }
+    /// Limits the list to holding at most the given number of elements
+    ///
+    /// The limit is only enforced when inserting, so existing elements are not removed.
+    pub fn set_bound(&mut self, capacity: usize, eviction: Eviction) {
+        self.bound = Some((capacity, eviction));
+    }
+    /// Removes the limit set with [`TimedList::bounded`] or [`TimedList::set_bound`]
+    pub fn remove_bound(&mut self) {
+        self.bound = None;
+    }
+    /// Sets when the list implicitly removes timed-out elements
+    pub fn set_clean_policy(&mut self, policy: CleanPolicy) {
+        self.clean_policy = policy;
+        self.calls = 0;
+    }
+    /// Gets the [`CleanPolicy`] that determines when the list implicitly removes timed-out elements
+    pub fn clean_policy(&self) -> CleanPolicy {
+        self.clean_policy
+    }
+    /// Gets the maximum number of elements and the [`Eviction`] policy of a bounded list
+    pub fn bound(&self) -> Option<(usize, Eviction)> {
+        self.bound
+    }
+    /// Creates a new [`TimedList`] with space for at least the given number of elements
+    pub fn with_capacity(capacity: usize) -> TimedList<T> {
+        let mut list = TimedList::new();
+        list.reserve(capacity);
+        list
+    }
+    /// Reserves space for at least the given number of additional elements
+    pub fn reserve(&mut self, additional: usize) {
+        self.list.reserve(additional);
+        self.deadlines.reserve(additional);
+    }
+    /// Gets the number of elements the list can hold without reallocating
+    pub fn capacity(&self) -> usize {
+        self.list.capacity().min(self.deadlines.capacity())
+    }
+    /// Shrinks the capacity of the list as much as possible
+    ///
+    /// Timed-out elements are removed first.
+    pub fn shrink_to_fit(&mut self) {
+        self.clean();
+        self.list.shrink_to_fit();
+        self.deadlines.shrink_to_fit();
+    }
+    /// Inserts an element into the list with the given number of floating-point seconds
+    ///
+    /// Returns a [`Key`] that can be used to refer to the element later
+    ///
+    /// If the list is bounded with [`Eviction::Reject`] and full, the element is dropped
+    /// and `None` is returned. Use [`TimedList::try_insert`] to get the element back instead.
+    pub fn insert<D: ToDuration>(&mut self, element: T, time: D) -> Option<Key> {
+        self.try_insert_timer(element, Timer::set(time)).ok()
     }
     /// Inserts an element into the list with the given number of floating-point seconds
-    pub fn insert<D: ToDuration>(&mut self, element: T, time: D) {
-        self.list.push((Timer::set(time), element));
+    ///
+    /// Returns a [`Key`] that can be used to refer to the element later,
+    /// or gives the element back if the list is bounded with [`Eviction::Reject`] and full.
+    pub fn try_insert<D: ToDuration>(&mut self, element: T, time: D) -> Result<Key, T> {
+        self.try_insert_timer(element, Timer::set(time))
+    }
+    /// Inserts many elements into the list, all with the same number of floating-point seconds
+    ///
+    /// The current time is only read once for the whole batch.
+    /// Elements that do not fit in a list bounded with [`Eviction::Reject`] are dropped.
+    pub fn insert_many<I, D>(&mut self, elements: I, time: D)
+    where
+        I: IntoIterator<Item = T>,
+        D: ToDuration,
+    {
+        let timer = Timer::set(time);
+        let elements = elements.into_iter();
+        self.reserve(elements.size_hint().0);
+        for element in elements {
+            let _ = self.try_insert_timer(element, timer);
+        }
+    }
+    /// Inserts many elements into the list, each with its own number of floating-point seconds
+    ///
+    /// The current time is only read once for the whole batch.
+    /// Elements that do not fit in a list bounded with [`Eviction::Reject`] are dropped.
+    pub fn extend_with_ttls<I, D>(&mut self, elements: I)
+    where
+        I: IntoIterator<Item = (T, D)>,
+        D: ToDuration,
+    {
+        let start = Elapsed::start();
+        let elements = elements.into_iter();
+        self.reserve(elements.size_hint().0);
+        for (element, time) in elements {
+            let timer = Timer {
+                elapsed: start,
+                duration: time.to_duration(),
+            };
+            let _ = self.try_insert_timer(element, timer);
+        }
+    }
+    /// Inserts an element into the list that will time out at the given [`Instant`]
+    ///
+    /// Returns a [`Key`] that can be used to refer to the element later,
+    /// or `None` if the list is bounded with [`Eviction::Reject`] and full.
+    pub fn insert_at(&mut self, element: T, deadline: Instant) -> Option<Key> {
+        self.try_insert_timer(element, Timer::until(deadline)).ok()
+    }
+    /// Moves all elements that have not timed out from another [`TimedList`] into this one, leaving the other empty
+    ///
+    /// Each element keeps its original deadline. The moved elements are given new [`Key`]s,
+    /// so [`Key`]s from the other list cannot be used with this one.
+    ///
+    /// If this list is bounded, elements are moved one at a time as if inserted, making room
+    /// according to its [`Eviction`] policy. Elements that a list bounded with [`Eviction::Reject`]
+    /// has no room for are left in the other list.
+    pub fn append(&mut self, other: &mut TimedList<T>) {
+        other.clean();
+        if self.bound.is_some() {
+            let mut rejected = Vec::new();
+            for entry in other.list.drain(..) {
+                let (key, timer) = (entry.key, entry.timer);
+                if let Err(elem) = self.try_insert_timer(entry.elem, timer) {
+                    rejected.push(Entry { key, timer, elem });
+                }
+            }
+            other.list = rejected;
+            other.retrack();
+            return;
+        }
+        self.reserve(other.list.len());
+        for mut entry in other.list.drain(..) {
+            entry.key = Key(self.next_key);
+            self.next_key += 1;
+            self.list.push(entry);
+        }
+        if self.order == ListOrder::Expiration {
+            self.list.sort_by_key(|entry| entry.timer.ends_at());
+        }
+        self.deadlines.append(&mut other.deadlines);
+        self.deadlines.sort();
+        other.expired.reset();
+    }
+    /// Moves the list to a background thread that removes elements as soon as they time out
+    ///
+    /// Timed-out elements are sent over the returned [`std::sync::mpsc::Receiver`].
+    /// See [`ExpiryNotifier`] for details.
+    pub fn spawn_cleaner(self) -> (ExpiryNotifier<T>, std::sync::mpsc::Receiver<T>)
+    where
+        T: Send + 'static,
+    {
+        ExpiryNotifier::from_list(self)
+    }
+    /// Gets the [`ListOrder`] in which the list keeps its elements
+    pub fn order(&self) -> ListOrder {
+        self.order
+    }
+    /// Removes the element with the given [`Key`] from the list and returns it
+    ///
+    /// Returns `None` if the element has timed out or was already removed.
+    pub fn remove(&mut self, key: Key) -> Option<T> {
+        self.auto_clean(false);
+        let i = self.position(key)?;
+        Some(self.take_entry(i).elem)
+    }
+    /// Gets a reference to the element at the given index among elements that have not timed out
+    pub fn get(&self, index: usize) -> Option<&T> {
+        self.live_position(index).map(|i| &self.list[i].elem)
+    }
+    /// Gets a mutable reference to the element at the given index among elements that have not timed out
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        let i = self.live_position(index)?;
+        Some(&mut self.list[i].elem)
+    }
+    /// Removes the element at the given index among elements that have not timed out and returns it
+    pub fn remove_index(&mut self, index: usize) -> Option<T> {
+        self.auto_clean(false);
+        let i = self.live_position(index)?;
+        Some(self.take_entry(i).elem)
+    }
+    /// Gets a reference to the element that was inserted first among elements that have not timed out
+    ///
+    /// This is by insertion order regardless of the list's [`ListOrder`].
+    /// Elements moved in by [`TimedList::append`] count as inserted when they were moved.
+    pub fn first(&self) -> Option<&T> {
+        let now = Instant::now();
+        self.list
+            .iter()
+            .filter(|entry| entry.timer.ends_at() >= now)
+            .min_by_key(|entry| entry.key)
+            .map(|entry| &entry.elem)
+    }
+    /// Gets a reference to the element that was inserted last among elements that have not timed out
+    ///
+    /// This is by insertion order regardless of the list's [`ListOrder`].
+    /// Elements moved in by [`TimedList::append`] count as inserted when they were moved.
+    pub fn last(&self) -> Option<&T> {
+        let now = Instant::now();
+        self.list
+            .iter()
+            .filter(|entry| entry.timer.ends_at() >= now)
+            .max_by_key(|entry| entry.key)
+            .map(|entry| &entry.elem)
+    }
+    /// Adds time to the element with the given [`Key`]
+    ///
+    /// Returns `false` if the element has timed out or was already removed.
+    pub fn extend_ttl<D: ToDuration>(&mut self, key: Key, time: D) -> bool {
+        self.auto_clean(false);
+        if let Some(i) = self.position(key) {
+            let mut entry = self.take_entry(i);
+            entry.timer.duration += time.to_duration();
+            self.place_entry(entry, i);
+            true
+        } else {
+            false
+        }
+    }
+    /// Adds time to all elements that have not timed out
+    pub fn extend_all<D: ToDuration>(&mut self, time: D) {
+        let now = Instant::now();
+        let time = time.to_duration();
+        for entry in &mut self.list {
+            if entry.timer.ends_at() >= now {
+                entry.timer.duration += time;
+            }
+        }
+        self.retrack();
+    }
+    /// Multiplies the time left of all elements that have not timed out by the given factor
+    ///
+    /// # Panics
+    ///
+    /// Panics if the factor is negative or not finite
+    pub fn scale_all(&mut self, factor: f64) {
+        let now = Instant::now();
+        for entry in &mut self.list {
+            if entry.timer.ends_at() < now {
+                continue;
+            }
+            let left = entry.timer.ends_at() - now;
+            let elapsed = now.saturating_duration_since(entry.timer.started_at());
+            entry.timer.duration = elapsed + left.mul_f64(factor);
+        }
+        self.retrack();
+    }
+    /// Restarts the countdown of the element with the given [`Key`] from its original [`Duration`]
+    ///
+    /// Touching an element each time it is used gives it idle-timeout semantics,
+    /// where it only times out after not being used for its [`Duration`].
+    ///
+    /// Returns `false` if the element has timed out or was already removed.
+    pub fn touch(&mut self, key: Key) -> bool {
+        self.auto_clean(false);
+        if let Some(i) = self.position(key) {
+            let mut entry = self.take_entry(i);
+            entry.timer.reset();
+            self.place_entry(entry, i);
+            true
+        } else {
+            false
+        }
+    }
+    /// Gets the time left for the element with the given [`Key`]
+    ///
+    /// Returns `None` if the element has timed out or was already removed.
+    pub fn remaining(&self, key: Key) -> Option<Duration> {
+        self.position(key)
+            .and_then(|i| self.list[i].timer.duration_left())
+    }
+    /// Gets the [`Instant`] at which the next element will time out
+    ///
+    /// Returns `None` if there are no elements that have not timed out.
+    pub fn next_expiration(&self) -> Option<Instant> {
+        self.deadlines.get(self.expired_count()).copied()
+    }
+    /// Gets the time left until the next element times out as a [`Duration`]
+    ///
+    /// Returns `None` if there are no elements that have not timed out.
+    pub fn next_expires_in(&self) -> Option<Duration> {
+        self.next_expiration()
+            .map(|deadline| deadline.saturating_duration_since(Instant::now()))
+    }
+    /// Blocks the current thread until the next element times out, then removes it from the list and returns it
+    ///
+    /// If an element has already timed out but has not yet been removed, it is returned immediately.
+    /// Returns `None` if the list is empty.
+    pub fn wait_next(&mut self) -> Option<T> {
+        let (i, entry) = self
+            .list
+            .iter()
+            .enumerate()
+            .min_by_key(|(_, entry)| entry.timer.ends_at())?;
+        if let Some(left) = entry.timer.duration_left() {
+            std::thread::sleep(left);
+        }
+        Some(self.take_entry(i).elem)
+    }
+    /// Sets a callback that is called with each element that is removed because its [`Duration`] has elapsed
+    ///
+    /// The callback is invoked by [`TimedList::clean`]. Besides explicit calls, the list cleans itself
+    /// when its [`CleanPolicy`] calls for it, when a bounded list is full on insertion,
+    /// when it is appended to another list, when it is turned into an iterator,
+    /// and in [`TimedList::shrink_to_fit`]. Under [`CleanPolicy::Never`], the callback is therefore
+    /// only called when the list is cleaned explicitly or one of those other cases applies.
+    /// Elements removed by [`TimedList::take_expired`], [`TimedList::drain_expired`],
+    /// or [`TimedList::wait_next`] are returned instead.
+    pub fn set_on_expire<F>(&mut self, f: F)
+    where
+        F: Fn(T) + Send + Sync + 'static,
+    {
+        self.on_expire = Some(OnExpire(Arc::new(f)));
+    }
+    /// Removes the callback set with [`TimedList::set_on_expire`]
+    pub fn clear_on_expire(&mut self) {
+        self.on_expire = None;
     }
     /// Forces the removal of all elements whose [`Duration`] has elpased.
     /// This method does not need to be called manually unless you
-    /// want to explicitely free the memory of timed-out elements immediately.
+    /// want to explicitely free the memory of timed-out elements immediately
+    /// or want the expiration callback to be called.
     pub fn clean(&mut self) {
-        self.list.retain(|(timer, _)| !timer.is_ready());
+        if let Some(on_expire) = self.on_expire.clone() {
+            for elem in self.take_expired() {
+                (on_expire.0)(elem);
+            }
+        } else {
+            let now = Instant::now();
+            match self.order {
+                ListOrder::Insertion => self.list.retain(|entry| entry.timer.ends_at() >= now),
+                ListOrder::Expiration => {
+                    let expired = self
+                        .list
+                        .partition_point(|entry| entry.timer.ends_at() < now);
+                    self.list.drain(..expired);
+                }
+            }
+            let expired = self.deadlines.partition_point(|deadline| *deadline < now);
+            self.deadlines.drain(..expired);
+            self.expired.reset();
+        }
+    }
+    /// Removes all elements whose [`Duration`] has elapsed and returns them in insertion order
+    pub fn take_expired(&mut self) -> Vec<T> {
+        let now = Instant::now();
+        let expired: Vec<_> = match self.order {
+            ListOrder::Insertion => {
+                let (expired, live) = std::mem::take(&mut self.list)
+                    .into_iter()
+                    .partition(|entry| entry.timer.ends_at() < now);
+                self.list = live;
+                expired
+            }
+            ListOrder::Expiration => {
+                let expired = self
+                    .list
+                    .partition_point(|entry| entry.timer.ends_at() < now);
+                self.list.drain(..expired).collect()
+            }
+        };
+        self.deadlines.drain(..expired.len());
+        self.expired.reset();
+        expired.into_iter().map(|entry| entry.elem).collect()
+    }
+    /// Removes all elements whose [`Duration`] has elapsed and iterates over them in insertion order
+    ///
+    /// Unlike [`TimedList::clean`], this allows timed-out elements to be acted upon rather than simply dropped.
+    pub fn drain_expired(&mut self) -> impl DoubleEndedIterator<Item = T> {
+        self.take_expired().into_iter()
     }
     /// Removes all elements from the list
     pub fn clear(&mut self) {
         self.list.clear();
+        self.deadlines.clear();
+        self.expired.reset();
     }
     /// Gets the number of elements in the list that have not timed out.
+    ///
+    /// This runs in constant time unless elements have timed out since the last call,
+    /// in which case it takes logarithmic time to find how many.
     pub fn len(&self) -> usize {
-        self.iter().count()
+        self.deadlines.len() - self.expired_count()
     }
     /// Check if the list is empty or if all existing elements have timed out.
+    ///
+    /// This runs in constant time.
     pub fn is_empty(&self) -> bool {
-        self.len() == 0
+        match self.deadlines.last() {
+            Some(deadline) => *deadline < Instant::now(),
+            None => true,
+        }
     }
     /// Retains elements in the list that match the predicate
     pub fn retain<F>(&mut self, mut f: F)
     where
         F: FnMut(&T) -> bool,
     {
-        self.list.retain(|(_, elem)| f(elem));
+        self.list.retain(|entry| f(&entry.elem));
+        self.retrack();
+    }
+    /// Retains elements in the list that match the predicate, which also receives each element's [`Timer`]
+    ///
+    /// This allows elements to be removed based on how much time they have left.
+    pub fn retain_with_timer<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&T, &Timer) -> bool,
+    {
+        self.list.retain(|entry| f(&entry.elem, &entry.timer));
+        self.retrack();
     }
     /// Iterates immutably through all elements.
     ///
@@ -379,15 +1596,13 @@ impl<T> TimedList<T> {
     /// may have been valid when iteration began may be skipped
     /// when they are actually iterated over.
     pub fn iter(&self) -> impl DoubleEndedIterator<Item = &T> {
-        self.list.iter().filter_map(
-            |(timer, elem)| {
-                if timer.is_ready() {
-                    None
-                } else {
-                    Some(elem)
-                }
-            },
-        )
+        self.list.iter().filter_map(|entry| {
+            if entry.timer.is_ready() {
+                None
+            } else {
+                Some(&entry.elem)
+            }
+        })
     }
     /// Iterates mutably through all elements.
     ///
@@ -395,16 +1610,99 @@ impl<T> TimedList<T> {
     /// may have been valid when iteration began may be skipped
     /// when they are actually iterated over.
     pub fn iter_mut(&mut self) -> impl DoubleEndedIterator<Item = &mut T> {
-        self.clean();
-        self.list.iter_mut().filter_map(
-            |(timer, elem)| {
-                if timer.is_ready() {
-                    None
-                } else {
-                    Some(elem)
-                }
-            },
-        )
+        self.auto_clean(true);
+        self.list.iter_mut().filter_map(|entry| {
+            if entry.timer.is_ready() {
+                None
+            } else {
+                Some(&mut entry.elem)
+            }
+        })
+    }
+    /// Iterates immutably through all elements and the time they have left.
+    ///
+    /// The current time is read once when this method is called, so all elements
+    /// are checked against the same moment no matter how long iteration takes.
+    pub fn iter_remaining(&self) -> impl DoubleEndedIterator<Item = (&T, Duration)> {
+        let now = Instant::now();
+        self.list.iter().filter_map(move |entry| {
+            let deadline = entry.timer.ends_at();
+            if deadline < now {
+                None
+            } else {
+                Some((&entry.elem, deadline - now))
+            }
+        })
+    }
+    /// Iterates immutably through the elements that will time out within the given number of floating-point seconds.
+    ///
+    /// This is useful for highlighting elements that are about to disappear.
+    /// The current time is read once when this method is called, so all elements
+    /// are checked against the same moment no matter how long iteration takes.
+    pub fn iter_expiring_within<D: ToDuration>(
+        &self,
+        time: D,
+    ) -> impl DoubleEndedIterator<Item = (&T, Duration)> {
+        let time = time.to_duration();
+        self.iter_remaining()
+            .filter(move |(_, remaining)| *remaining <= time)
+    }
+    /// Iterates immutably through the elements that were inserted or last touched
+    /// more than the given number of floating-point seconds ago.
+    ///
+    /// Elements that have timed out are not included.
+    /// The current time is read once when this method is called, so all elements
+    /// are checked against the same moment no matter how long iteration takes.
+    pub fn iter_older_than<D: ToDuration>(&self, time: D) -> impl DoubleEndedIterator<Item = &T> {
+        let time = time.to_duration();
+        let now = Instant::now();
+        self.list.iter().filter_map(move |entry| {
+            if entry.timer.ends_at() < now
+                || now.saturating_duration_since(entry.timer.started_at()) <= time
+            {
+                None
+            } else {
+                Some(&entry.elem)
+            }
+        })
+    }
+    /// Iterates mutably through all elements and the time they have left.
+    ///
+    /// The current time is read once when this method is called, so all elements
+    /// are checked against the same moment no matter how long iteration takes.
+    pub fn iter_remaining_mut(&mut self) -> impl DoubleEndedIterator<Item = (&mut T, Duration)> {
+        self.auto_clean(true);
+        let now = Instant::now();
+        self.list.iter_mut().filter_map(move |entry| {
+            let deadline = entry.timer.ends_at();
+            if deadline < now {
+                None
+            } else {
+                Some((&mut entry.elem, deadline - now))
+            }
+        })
+    }
+    /// Iterates immutably through all elements, soonest to time out first.
+    ///
+    /// Elements that time out at the same time are iterated over in list order.
+    /// If the list is not kept in [`ListOrder::Expiration`], this method allocates to sort the elements.
+    pub fn iter_by_remaining(&self) -> impl DoubleEndedIterator<Item = &T> {
+        let now = Instant::now();
+        let mut entries: Vec<&Entry<T>> = self
+            .list
+            .iter()
+            .filter(|entry| entry.timer.ends_at() >= now)
+            .collect();
+        if self.order == ListOrder::Insertion {
+            entries.sort_by_key(|entry| entry.timer.ends_at());
+        }
+        entries.into_iter().map(|entry| &entry.elem)
+    }
+    /// Sorts the elements so that those that will time out soonest come first
+    ///
+    /// Elements inserted afterwards are still placed according to the list's [`ListOrder`].
+    pub fn sort_by_expiration(&mut self) {
+        self.list.sort_by_key(|entry| entry.timer.ends_at());
     }
     /// Iterates immutably through all elements and their timers.
     ///
@@ -414,11 +1712,11 @@ impl<T> TimedList<T> {
     /// may have been valid when iteration began may be skipped
     /// when they are actually iterated over.
     pub fn timer_iter(&self) -> impl DoubleEndedIterator<Item = (&T, Timer)> {
-        self.list.iter().filter_map(|(timer, elem)| {
-            if timer.is_ready() {
+        self.list.iter().filter_map(|entry| {
+            if entry.timer.is_ready() {
                 None
             } else {
-                Some((elem, *timer))
+                Some((&entry.elem, entry.timer))
             }
         })
     }
@@ -428,24 +1726,187 @@ impl<T> TimedList<T> {
     /// may have been valid when iteration began may be skipped
     /// when they are actually iterated over.
     pub fn timer_iter_mut(&mut self) -> impl DoubleEndedIterator<Item = (&mut T, Timer)> {
-        self.clean();
-        self.list.iter_mut().filter_map(|(timer, elem)| {
-            if timer.is_ready() {
+        self.auto_clean(true);
+        self.list.iter_mut().filter_map(|entry| {
+            if entry.timer.is_ready() {
+                None
+            } else {
+                Some((&mut entry.elem, entry.timer))
+            }
+        })
+    }
+    /// Cleans the list if its [`CleanPolicy`] calls for it
+    fn auto_clean(&mut self, iterating: bool) {
+        let clean = match self.clean_policy {
+            CleanPolicy::MutableIteration => iterating,
+            CleanPolicy::EveryN(n) => {
+                self.calls += 1;
+                if self.calls >= n {
+                    self.calls = 0;
+                    true
+                } else {
+                    false
+                }
+            }
+            CleanPolicy::ExpiredRatio(ratio) => {
+                self.expired_count() as f64 > ratio * self.deadlines.len() as f64
+            }
+            CleanPolicy::Never => false,
+        };
+        if clean {
+            self.clean();
+        }
+    }
+    /// Finds the index of the live element with the given [`Key`]
+    fn position(&self, key: Key) -> Option<usize> {
+        self.list
+            .iter()
+            .position(|entry| entry.key == key && !entry.timer.is_ready())
+    }
+    /// Inserts an element with the given [`Timer`], making room if the list is bounded
+    fn try_insert_timer(&mut self, element: T, timer: Timer) -> Result<Key, T> {
+        self.auto_clean(false);
+        if let Some((capacity, eviction)) = self.bound {
+            if self.list.len() >= capacity {
+                self.clean();
+            }
+            while self.list.len() >= capacity {
+                let evicted = match eviction {
+                    Eviction::SoonestToExpire => self
+                        .list
+                        .iter()
+                        .enumerate()
+                        .min_by_key(|(_, entry)| entry.timer.ends_at()),
+                    Eviction::OldestInserted => self
+                        .list
+                        .iter()
+                        .enumerate()
+                        .min_by_key(|(_, entry)| entry.key),
+                    Eviction::Reject => None,
+                };
+                match evicted {
+                    Some((i, _)) => {
+                        self.take_entry(i);
+                    }
+                    None => return Err(element),
+                }
+            }
+        }
+        let key = Key(self.next_key);
+        self.next_key += 1;
+        let entry = Entry {
+            key,
+            timer,
+            elem: element,
+        };
+        self.place_entry(entry, self.list.len());
+        Ok(key)
+    }
+    /// Removes the entry at the given index
+    fn take_entry(&mut self, i: usize) -> Entry<T> {
+        let entry = self.list.remove(i);
+        self.untrack(entry.timer.ends_at());
+        entry
+    }
+    /// Inserts an entry at the given index, or where its deadline belongs if the list is ordered by expiration
+    fn place_entry(&mut self, entry: Entry<T>, i: usize) {
+        let deadline = entry.timer.ends_at();
+        self.track(deadline);
+        let i = match self.order {
+            ListOrder::Insertion => i,
+            ListOrder::Expiration => self
+                .list
+                .partition_point(|entry| entry.timer.ends_at() <= deadline),
+        };
+        self.list.insert(i, entry);
+    }
+    /// Finds the index in the list of the element at the given index among live elements
+    fn live_position(&self, index: usize) -> Option<usize> {
+        let expired = self.expired_count();
+        if expired > 0 && self.order == ListOrder::Insertion {
+            let now = Instant::now();
+            return self
+                .list
+                .iter()
+                .enumerate()
+                .filter(|(_, entry)| entry.timer.ends_at() >= now)
+                .nth(index)
+                .map(|(i, _)| i);
+        }
+        let i = match self.order {
+            ListOrder::Insertion => index,
+            ListOrder::Expiration => expired + index,
+        };
+        Some(i).filter(|&i| i < self.list.len())
+    }
+    /// Gets the number of tracked deadlines that have passed
+    fn expired_count(&self) -> usize {
+        self.expired.count(&self.deadlines)
+    }
+    /// Adds a deadline to the sorted deadlines
+    fn track(&mut self, deadline: Instant) {
+        let i = self.deadlines.partition_point(|d| *d <= deadline);
+        self.deadlines.insert(i, deadline);
+    }
+    /// Removes a deadline from the sorted deadlines
+    fn untrack(&mut self, deadline: Instant) {
+        if let Ok(i) = self.deadlines.binary_search(&deadline) {
+            self.deadlines.remove(i);
+            self.expired.removed(i);
+        }
+    }
+    /// Rebuilds the sorted deadlines from the list
+    fn retrack(&mut self) {
+        self.deadlines = self
+            .list
+            .iter()
+            .map(|entry| entry.timer.ends_at())
+            .collect();
+        self.deadlines.sort_unstable();
+        self.expired.reset();
+    }
+    /// Iterates through the elements that have not timed out at the given [`Instant`] and their deadlines
+    fn live_entries(&self, now: Instant) -> impl Iterator<Item = (&T, Instant)> {
+        self.list.iter().filter_map(move |entry| {
+            let deadline = entry.timer.ends_at();
+            if deadline < now {
                 None
             } else {
-                Some((elem, *timer))
+                Some((&entry.elem, deadline))
             }
         })
     }
 }
 
+impl<T: PartialEq> PartialEq for TimedList<T> {
+    fn eq(&self, other: &Self) -> bool {
+        let now = Instant::now();
+        self.live_entries(now).eq(other.live_entries(now))
+    }
+}
+
+impl<T: PartialOrd> PartialOrd for TimedList<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        let now = Instant::now();
+        self.live_entries(now).partial_cmp(other.live_entries(now))
+    }
+}
+
 impl<T, D> std::iter::FromIterator<(T, D)> for TimedList<T>
 where
     D: ToDuration,
 {
     fn from_iter<I: IntoIterator<Item = (T, D)>>(iter: I) -> Self {
-        TimedList {
-            list: iter.into_iter().map(|(x, d)| (Timer::set(d), x)).collect(),
+        let mut list = TimedList::new();
+        list.extend_with_ttls(iter);
+        list
+    }
+}
+
+impl<T> Extend<TimedList<T>> for TimedList<T> {
+    fn extend<I: IntoIterator<Item = TimedList<T>>>(&mut self, iter: I) {
+        for mut other in iter {
+            self.append(&mut other);
         }
     }
 }
@@ -458,15 +1919,13 @@ where
     type IntoIter = Box<dyn DoubleEndedIterator<Item = T>>;
     fn into_iter(mut self) -> Self::IntoIter {
         self.clean();
-        Box::new(self.list.into_iter().filter_map(
-            |(timer, elem)| {
-                if timer.is_ready() {
-                    None
-                } else {
-                    Some(elem)
-                }
-            },
-        ))
+        Box::new(self.list.into_iter().filter_map(|entry| {
+            if entry.timer.is_ready() {
+                None
+            } else {
+                Some(entry.elem)
+            }
+        }))
     }
 }
 
@@ -489,10 +1948,240 @@ println!("Printing all those numbers took {} seconds", elapsed);
 ```
 */
 pub fn measure<F>(f: F) -> Seconds
+where
+    F: FnOnce(),
+{
+    Seconds::from_duration(measure_duration(f))
+}
+
+/// Measure the amount of time the given function takes to execute as a [`Duration`]
+///
+/// Unlike [`measure`], this does not lose precision when timing long-running work.
+pub fn measure_duration<F>(f: F) -> Duration
 where
     F: FnOnce(),
 {
     let timer = Elapsed::start();
     f();
+    timer.duration()
+}
+
+static UPTIME: OnceLock<Elapsed> = OnceLock::new();
+
+/**
+Get how long the process has been running in floating-point seconds
+
+The uptime is counted from the first call to this function or [`uptime_duration`],
+so call one of them early in `main` for the most accurate result.
+
+# Example
+```
+use eggtimer::uptime;
+
+uptime();
+// ...
+println!("Running for {} seconds", uptime());
+```
+*/
+pub fn uptime() -> Seconds {
+    Seconds::from_duration(uptime_duration())
+}
+
+/// Get how long the process has been running as a [`Duration`]
+///
+/// See [`uptime`] for when the uptime starts being counted.
+pub fn uptime_duration() -> Duration {
+    UPTIME.get_or_init(Elapsed::start).duration()
+}
+
+/**
+Measure the amount of time the given future takes to complete
+
+Time is measured in floating-point number of seconds.
+This includes any time the future spends waiting to be polled.
+
+# Example
+```
+use eggtimer::measure_async;
+
+async fn handle_request() {
+    let elapsed = measure_async(async {
+        // Do some asynchronous work
+    })
+    .await;
+    println!("Handling the request took {} seconds", elapsed);
+}
+```
+*/
+pub async fn measure_async<F>(fut: F) -> Seconds
+where
+    F: std::future::Future<Output = ()>,
+{
+    let timer = Elapsed::start();
+    fut.await;
     timer.seconds()
 }
+
+/// Measure the amount of time the given future takes to complete
+/// and return its output along with the elapsed [`Duration`]
+pub async fn measure_async_result<F>(fut: F) -> (F::Output, Duration)
+where
+    F: std::future::Future,
+{
+    let timer = Elapsed::start();
+    let output = fut.await;
+    (output, timer.duration())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Gets an [`Instant`] that has already passed
+    fn past() -> Instant {
+        Instant::now() - Duration::from_millis(1)
+    }
+
+    #[test]
+    fn elements_expire_in_deadline_order() {
+        let mut list = TimedList::with_order(ListOrder::Expiration);
+        list.insert('c', 30.0);
+        list.insert('a', 10.0);
+        list.insert('b', 20.0);
+        list.insert_at('x', past());
+        assert_eq!(list.iter().copied().collect::<String>(), "abc");
+        assert_eq!(list.len(), 3);
+        assert_eq!(list.take_expired(), ['x']);
+        assert_eq!(list.first(), Some(&'c'));
+        assert_eq!(list.last(), Some(&'b'));
+        let mut list = TimedList::new();
+        list.insert('c', 30.0);
+        list.insert('a', 10.0);
+        list.insert('b', 20.0);
+        assert_eq!(list.iter().copied().collect::<String>(), "cab");
+        assert_eq!(list.iter_by_remaining().copied().collect::<String>(), "abc");
+        assert!(list.next_expires_in().unwrap() <= Duration::from_secs(10));
+    }
+
+    #[test]
+    fn bounded_lists_evict() {
+        let mut list = TimedList::bounded(2, Eviction::SoonestToExpire);
+        list.insert(1, 20.0);
+        list.insert(2, 10.0);
+        list.insert(3, 30.0);
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), [1, 3]);
+        let mut list = TimedList::bounded(2, Eviction::OldestInserted);
+        list.insert(1, 20.0);
+        list.insert(2, 10.0);
+        list.insert(3, 30.0);
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), [2, 3]);
+        let mut list = TimedList::bounded(2, Eviction::Reject);
+        list.insert(1, 20.0);
+        list.insert(2, 10.0);
+        assert_eq!(list.try_insert(3, 30.0), Err(3));
+        assert_eq!(list.insert(3, 30.0), None);
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), [1, 2]);
+        // Timed-out elements are removed before anything is evicted
+        let mut list = TimedList::bounded(2, Eviction::Reject);
+        list.insert_at(1, past());
+        list.insert(2, 10.0);
+        assert!(list.try_insert(3, 30.0).is_ok());
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), [2, 3]);
+    }
+
+    #[test]
+    fn keys_stay_valid_after_cleaning() {
+        for order in [ListOrder::Insertion, ListOrder::Expiration] {
+            let mut list = TimedList::with_order(order);
+            let a = list.insert_at('a', past()).unwrap();
+            let b = list.insert('b', 60.0).unwrap();
+            let c = list.insert_at('c', past()).unwrap();
+            let d = list.insert('d', 30.0).unwrap();
+            list.clean();
+            assert_eq!(list.len(), 2);
+            assert_eq!(list.remove(a), None);
+            assert_eq!(list.remove(c), None);
+            assert!(list.extend_ttl(b, 1.0));
+            assert!(list.remaining(b).unwrap() > Duration::from_secs(60));
+            assert_eq!(list.remove(d), Some('d'));
+            assert_eq!(list.iter().copied().collect::<String>(), "b");
+        }
+    }
+
+    #[test]
+    fn equality_ignores_bookkeeping() {
+        let deadline = Instant::now() + Duration::from_secs(60);
+        let mut a = TimedList::new();
+        a.insert_at(1, deadline);
+        let mut b = TimedList::new();
+        let removed = b.insert(0, 60.0).unwrap();
+        b.insert_at(1, deadline);
+        b.insert_at(2, past());
+        b.remove(removed);
+        assert_eq!(a, b);
+        b.clean();
+        assert_eq!(a, b);
+        b.insert_at(3, deadline);
+        assert_ne!(a, b);
+        assert!(a < b);
+    }
+
+    #[test]
+    fn len_skips_expired_elements() {
+        let mut list = TimedList::new();
+        list.set_clean_policy(CleanPolicy::Never);
+        list.insert_at('a', past());
+        let b = list.insert('b', 60.0).unwrap();
+        list.insert_at('c', past());
+        list.insert('d', 30.0);
+        assert_eq!(list.len(), 2);
+        assert_eq!(list.len(), 2);
+        list.remove(b);
+        assert_eq!(list.len(), 1);
+        list.insert_at('e', past());
+        assert_eq!(list.len(), 1);
+        let mut other = TimedList::new();
+        other.insert_at('f', past());
+        other.insert('g', 60.0);
+        assert_eq!(other.len(), 1);
+        list.append(&mut other);
+        assert_eq!(other.len(), 0);
+        assert_eq!(list.len(), 2);
+        list.retain(|c| *c != 'g');
+        assert_eq!(list.len(), 1);
+        list.clean();
+        assert_eq!(list.len(), 1);
+    }
+
+    #[test]
+    fn append_respects_bound() {
+        let mut list = TimedList::bounded(3, Eviction::Reject);
+        list.insert(1, 60.0);
+        let mut other: TimedList<_> = vec![(2, 60.0), (3, 60.0), (4, 60.0)].into_iter().collect();
+        list.append(&mut other);
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), [1, 2, 3]);
+        assert_eq!(other.iter().copied().collect::<Vec<_>>(), [4]);
+        assert_eq!(other.len(), 1);
+        let mut list = TimedList::bounded(2, Eviction::OldestInserted);
+        list.insert(1, 60.0);
+        let mut other: TimedList<_> = vec![(2, 60.0), (3, 60.0)].into_iter().collect();
+        list.append(&mut other);
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), [2, 3]);
+        assert!(other.is_empty());
+    }
+
+    #[test]
+    fn extend_merges_lists() {
+        let mut a = TimedList::new();
+        let key = a.insert(1, 60.0).unwrap();
+        let mut b = TimedList::new();
+        b.insert(2, 60.0);
+        b.insert_at(3, past());
+        let mut c = TimedList::new();
+        c.insert(4, 60.0);
+        a.extend(vec![b, c]);
+        assert_eq!(a.iter().copied().collect::<Vec<_>>(), [1, 2, 4]);
+        assert_eq!(a.len(), 3);
+        assert!(a.extend_ttl(key, 1.0));
+    }
+}
@@ -0,0 +1,77 @@
+/**
+Time how long a block of code takes to execute
+
+Evaluates to a tuple of the block's value and the elapsed [`Duration`](std::time::Duration).
+
+# Example
+```
+use eggtimer::time;
+
+let (sum, elapsed) = time! {
+    (0..1000u64).sum::<u64>()
+};
+
+println!("Computed {} in {:?}", sum, elapsed);
+```
+*/
+#[macro_export]
+macro_rules! time {
+    ($($body:tt)*) => {{
+        let timer = $crate::Elapsed::start();
+        let value = { $($body)* };
+        (value, timer.duration())
+    }};
+}
+
+/**
+Time how long a block of code takes to execute and print the elapsed time with a label
+
+The elapsed time is printed to standard error. Evaluates to a tuple of the block's
+value and the elapsed [`Duration`](std::time::Duration).
+
+# Example
+```
+use eggtimer::time_named;
+
+let (sum, _) = time_named!("sum", {
+    (0..1000u64).sum::<u64>()
+});
+```
+*/
+#[macro_export]
+macro_rules! time_named {
+    ($label:expr, $body:block) => {{
+        let (value, elapsed) = $crate::time!($body);
+        eprintln!("{} took {:?}", $label, elapsed);
+        (value, elapsed)
+    }};
+}
+
+/**
+Time a block of code and record the elapsed time under a label in the global profiling registry
+
+Evaluates to the block's value. Timings can be retrieved with [`profile_report`](crate::profile_report).
+
+Unless the `profiling` feature is enabled, this macro does nothing but evaluate the block,
+so instrumentation can be left in production code at no cost.
+
+# Example
+```
+use eggtimer::profile;
+
+let sum = profile!("sum", {
+    (0..1000u64).sum::<u64>()
+});
+
+for (label, entry) in eggtimer::profile_report() {
+    println!("{}: {:?} over {} calls", label, entry.total(), entry.count());
+}
+```
+*/
+#[macro_export]
+macro_rules! profile {
+    ($label:expr, $body:block) => {{
+        let _scope = $crate::ProfileScope::new($label);
+        $body
+    }};
+}
@@ -0,0 +1,327 @@
+use std::{
+    hint::black_box,
+    sync::{Barrier, OnceLock},
+    thread,
+    time::{Duration, Instant},
+};
+
+use crate::{FromDuration, Seconds, Timer, ToDuration};
+
+static OVERHEAD: OnceLock<Duration> = OnceLock::new();
+
+const CALIBRATION_SAMPLES: usize = 1001;
+
+/// Times a single call of a function
+#[inline(never)]
+fn time_call<F>(f: F) -> Duration
+where
+    F: FnOnce(),
+{
+    let start = Instant::now();
+    f();
+    start.elapsed()
+}
+
+/**
+Estimate the overhead of timing a function with [`measure_calibrated`]
+
+This is the time it takes to read the clock twice and call an empty closure.
+The estimate is the median of many samples. It is computed the first time this
+function is called and reused afterwards.
+
+Calling this function before measuring anything avoids paying the cost of
+calibrating during the first call to [`measure_calibrated`].
+*/
+pub fn calibrate() -> Duration {
+    *OVERHEAD.get_or_init(|| {
+        let mut samples: Vec<Duration> = (0..CALIBRATION_SAMPLES)
+            .map(|_| time_call(|| black_box(())))
+            .collect();
+        samples.sort_unstable();
+        samples[samples.len() / 2]
+    })
+}
+
+/**
+Measure the amount of time the given function takes to execute, minus the overhead of measuring it
+
+Time is measured in floating-point number of seconds.
+The overhead is estimated by [`calibrate`]. The result is never negative.
+
+This is useful when measuring very short functions, whose run time would
+otherwise be dominated by the cost of reading the clock.
+*/
+pub fn measure_calibrated<F>(f: F) -> Seconds
+where
+    F: FnOnce(),
+{
+    let overhead = calibrate();
+    let elapsed = time_call(f);
+    Seconds::from_duration(elapsed.saturating_sub(overhead))
+}
+
+/**
+Run the given function `n` times and measure how long each call takes
+
+The returned [`Vec`] contains one [`Duration`] per call, in the order the calls were made.
+
+# Example
+```
+use eggtimer::measure_n;
+
+let samples = measure_n(100, || {
+    let _ = (0..1000).sum::<u32>();
+});
+
+let fastest = samples.iter().min().unwrap();
+println!("The fastest run took {:?}", fastest);
+```
+*/
+pub fn measure_n<F>(n: usize, mut f: F) -> Vec<Duration>
+where
+    F: FnMut(),
+{
+    (0..n).map(|_| time_call(&mut f)).collect()
+}
+
+/// The result of comparing the run times of two functions with [`compare`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Comparison {
+    first_mean: Duration,
+    second_mean: Duration,
+    ratio: f64,
+    interval: (f64, f64),
+}
+
+impl Comparison {
+    /// Gets the mean time a call to the first function took
+    pub fn first_mean(&self) -> Duration {
+        self.first_mean
+    }
+    /// Gets the mean time a call to the second function took
+    pub fn second_mean(&self) -> Duration {
+        self.second_mean
+    }
+    /// Gets how many times longer the first function takes than the second
+    ///
+    /// A ratio greater than 1 means the second function is faster.
+    /// This is the geometric mean of the ratios of each pair of runs.
+    pub fn ratio(&self) -> f64 {
+        self.ratio
+    }
+    /// Gets an approximate 95% confidence interval for [`Comparison::ratio`]
+    pub fn ratio_interval(&self) -> (f64, f64) {
+        self.interval
+    }
+    /// Checks if one function is faster than the other with 95% confidence,
+    /// which is the case when [`Comparison::ratio_interval`] does not contain 1
+    pub fn is_significant(&self) -> bool {
+        self.interval.0 > 1.0 || self.interval.1 < 1.0
+    }
+}
+
+/**
+Compare how long two functions take to execute
+
+Each function is called `iterations` times. Calls are interleaved, alternating which
+function goes first, so that effects like CPU frequency changes affect both equally.
+
+# Example
+```
+use eggtimer::compare;
+
+let comparison = compare(
+    || (0..1000u64).sum::<u64>(),
+    || (0..1000u64).fold(0, |a, b| a + b),
+    100,
+);
+
+println!(
+    "The first takes {:.2}x as long as the second (95% CI {:?})",
+    comparison.ratio(),
+    comparison.ratio_interval()
+);
+```
+*/
+pub fn compare<F, G, A, B>(mut f: F, mut g: G, iterations: usize) -> Comparison
+where
+    F: FnMut() -> A,
+    G: FnMut() -> B,
+{
+    let mut first_total = Duration::from_secs(0);
+    let mut second_total = Duration::from_secs(0);
+    let mut log_ratios = Vec::with_capacity(iterations);
+    for i in 0..iterations {
+        let (first, second) = if i % 2 == 0 {
+            let first = time_call(|| drop(black_box(f())));
+            let second = time_call(|| drop(black_box(g())));
+            (first, second)
+        } else {
+            let second = time_call(|| drop(black_box(g())));
+            let first = time_call(|| drop(black_box(f())));
+            (first, second)
+        };
+        first_total += first;
+        second_total += second;
+        let nanos = |d: Duration| (d.as_nanos() as f64).max(1.0);
+        log_ratios.push((nanos(first) / nanos(second)).ln());
+    }
+    let n = log_ratios.len() as f64;
+    let mean = log_ratios.iter().sum::<f64>() / n;
+    let interval = if log_ratios.len() < 2 {
+        (0.0, f64::INFINITY)
+    } else {
+        let variance = log_ratios.iter().map(|r| (r - mean).powi(2)).sum::<f64>() / (n - 1.0);
+        let margin = 1.96 * (variance / n).sqrt();
+        ((mean - margin).exp(), (mean + margin).exp())
+    };
+    let average = |total: Duration| {
+        Duration::from_nanos((total.as_nanos() / iterations.max(1) as u128) as u64)
+    };
+    Comparison {
+        first_mean: average(first_total),
+        second_mean: average(second_total),
+        ratio: if log_ratios.is_empty() {
+            1.0
+        } else {
+            mean.exp()
+        },
+        interval,
+    }
+}
+
+/**
+Repeatedly call the given function until the given number of floating-point seconds has elapsed
+
+Returns the number of times the function was called and the total elapsed [`Duration`].
+The function is always called at least once, and a call that is in progress when the
+time runs out is allowed to finish, so the total may slightly exceed the budget.
+
+# Example
+```
+use eggtimer::run_for;
+
+let (count, elapsed) = run_for(0.01, || {
+    let _ = (0..100).sum::<u32>();
+});
+
+println!("{} iterations in {:?}", count, elapsed);
+```
+*/
+pub fn run_for<D, F>(time: D, mut f: F) -> (u64, Duration)
+where
+    D: ToDuration,
+    F: FnMut(),
+{
+    let timer = Timer::set(time);
+    let mut count = 0;
+    loop {
+        f();
+        count += 1;
+        if timer.is_ready() {
+            break;
+        }
+    }
+    (count, timer.duration())
+}
+
+/// The result of running a function on several threads at once with [`measure_parallel`]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ParallelMeasurement {
+    baseline: Duration,
+    per_thread: Vec<Duration>,
+    wall_clock: Duration,
+}
+
+impl ParallelMeasurement {
+    /// Gets how long a single call took on the calling thread before the threads were started
+    pub fn baseline(&self) -> Duration {
+        self.baseline
+    }
+    /// Gets how long the function took on each thread, in the order the threads were spawned
+    pub fn per_thread(&self) -> &[Duration] {
+        &self.per_thread
+    }
+    /// Gets the time from just before the threads were released until the last one finished
+    pub fn wall_clock(&self) -> Duration {
+        self.wall_clock
+    }
+    /// Gets the sum of the times the function took on each thread
+    pub fn total(&self) -> Duration {
+        self.per_thread.iter().sum()
+    }
+    /// Gets how many times longer running the calls one after another would have taken
+    ///
+    /// This is the [baseline](ParallelMeasurement::baseline) times the number of threads,
+    /// divided by the wall-clock time. A speedup close to the number of threads means
+    /// the function scales well, while a speedup close to 1 or below means the threads
+    /// are mostly waiting on each other.
+    pub fn speedup(&self) -> f64 {
+        let wall_clock = self.wall_clock.as_secs_f64();
+        if wall_clock == 0.0 {
+            1.0
+        } else {
+            self.baseline.as_secs_f64() * self.per_thread.len() as f64 / wall_clock
+        }
+    }
+}
+
+/**
+Run the given function on `n_threads` threads at once and measure how long each call takes
+
+The function is first called once on the calling thread to measure a single-threaded baseline.
+Then all threads wait for each other before calling the function, so the calls overlap as much as possible.
+
+# Panics
+
+Panics if `n_threads` is 0, or if the function panics on any thread
+
+# Example
+```
+use std::sync::Mutex;
+use eggtimer::measure_parallel;
+
+let shared = Mutex::new(0u64);
+let measurement = measure_parallel(4, || {
+    for i in 0..1000 {
+        *shared.lock().unwrap() += i;
+    }
+});
+
+println!(
+    "{:?} wall-clock, {:.2}x speedup",
+    measurement.wall_clock(),
+    measurement.speedup()
+);
+```
+*/
+pub fn measure_parallel<F>(n_threads: usize, f: F) -> ParallelMeasurement
+where
+    F: Fn() + Sync,
+{
+    assert!(n_threads > 0, "measure_parallel needs at least 1 thread");
+    let baseline = time_call(&f);
+    let barrier = Barrier::new(n_threads + 1);
+    let (per_thread, wall_clock) = thread::scope(|scope| {
+        let handles: Vec<_> = (0..n_threads)
+            .map(|_| {
+                scope.spawn(|| {
+                    barrier.wait();
+                    time_call(&f)
+                })
+            })
+            .collect();
+        let start = Instant::now();
+        barrier.wait();
+        let per_thread: Vec<Duration> = handles
+            .into_iter()
+            .map(|handle| handle.join().expect("measured function panicked"))
+            .collect();
+        (per_thread, start.elapsed())
+    });
+    ParallelMeasurement {
+        baseline,
+        per_thread,
+        wall_clock,
+    }
+}
@@ -0,0 +1,173 @@
+use std::{
+    sync::{mpsc, Arc, Mutex, MutexGuard, PoisonError},
+    thread::{self, JoinHandle},
+    time::Duration,
+};
+
+use crate::{alarm::Alarm, Key, TimedList, ToDuration};
+
+/**
+A channel sender that timed-out elements and [`Timer`](crate::Timer) notifications can be sent over
+
+This is implemented for [`mpsc::Sender`]. The `crossbeam-channel` and `flume` features
+implement it for those crates' senders, whose receivers can be selected over
+and shared between threads.
+*/
+pub trait NotifySender<T>: Send + 'static {
+    /// Sends a value, returning `false` if the receiving side has disconnected
+    fn notify(&self, value: T) -> bool;
+}
+
+impl<T: Send + 'static> NotifySender<T> for mpsc::Sender<T> {
+    fn notify(&self, value: T) -> bool {
+        self.send(value).is_ok()
+    }
+}
+
+#[cfg(feature = "crossbeam-channel")]
+impl<T: Send + 'static> NotifySender<T> for crossbeam_channel::Sender<T> {
+    fn notify(&self, value: T) -> bool {
+        self.send(value).is_ok()
+    }
+}
+
+#[cfg(feature = "flume")]
+impl<T: Send + 'static> NotifySender<T> for flume::Sender<T> {
+    fn notify(&self, value: T) -> bool {
+        self.send(value).is_ok()
+    }
+}
+
+struct State<T> {
+    list: TimedList<T>,
+    stopped: bool,
+}
+
+struct Shared<T> {
+    state: Mutex<State<T>>,
+    alarm: Alarm,
+}
+
+impl<T> Shared<T> {
+    fn lock(&self) -> MutexGuard<'_, State<T>> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// A [`TimedList`] that is cleaned on a background thread
+///
+/// Elements whose [`Duration`] has elapsed are removed as soon as they time out
+/// and sent over the [`mpsc::Receiver`] returned when the [`ExpiryNotifier`] is created.
+///
+/// Dropping the [`ExpiryNotifier`] stops the background thread.
+pub struct ExpiryNotifier<T> {
+    shared: Arc<Shared<T>>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl<T> ExpiryNotifier<T>
+where
+    T: Send + 'static,
+{
+    /// Creates a new empty [`ExpiryNotifier`] and the [`mpsc::Receiver`] that timed-out elements are sent to
+    pub fn new() -> (ExpiryNotifier<T>, mpsc::Receiver<T>) {
+        ExpiryNotifier::from_list(TimedList::new())
+    }
+    /// Creates a new [`ExpiryNotifier`] from an existing [`TimedList`] and the [`mpsc::Receiver`] that timed-out elements are sent to
+    pub fn from_list(list: TimedList<T>) -> (ExpiryNotifier<T>, mpsc::Receiver<T>) {
+        let (sender, receiver) = mpsc::channel();
+        (ExpiryNotifier::with_sender(list, sender), receiver)
+    }
+    /**
+    Creates a new [`ExpiryNotifier`] from an existing [`TimedList`] that sends timed-out elements over the given [`NotifySender`]
+
+    # Example
+    ```
+    use eggtimer::{ExpiryNotifier, TimedList};
+
+    let (sender, receiver) = std::sync::mpsc::channel();
+    let notifier = ExpiryNotifier::with_sender(TimedList::new(), sender);
+    notifier.insert("expired", 0.01);
+    assert_eq!(receiver.recv(), Ok("expired"));
+    ```
+    */
+    pub fn with_sender<S>(list: TimedList<T>, sender: S) -> ExpiryNotifier<T>
+    where
+        S: NotifySender<T>,
+    {
+        let shared = Arc::new(Shared {
+            state: Mutex::new(State {
+                list,
+                stopped: false,
+            }),
+            alarm: Alarm::new(),
+        });
+        let thread_shared = Arc::clone(&shared);
+        let thread = thread::spawn(move || {
+            let shared = thread_shared;
+            let mut state = shared.lock();
+            while !state.stopped {
+                for elem in state.list.take_expired() {
+                    sender.notify(elem);
+                }
+                let timeout = state.list.next_expires_in();
+                state = shared.alarm.wait(&shared.state, state, timeout);
+            }
+        });
+        ExpiryNotifier {
+            shared,
+            thread: Some(thread),
+        }
+    }
+}
+
+impl<T> ExpiryNotifier<T> {
+    /// Inserts an element with the given number of floating-point seconds
+    ///
+    /// Returns a [`Key`] that can be used to refer to the element later,
+    /// or `None` if the list is bounded with [`Eviction::Reject`](crate::Eviction::Reject) and full.
+    pub fn insert<D: ToDuration>(&self, element: T, time: D) -> Option<Key> {
+        self.with_list(|list| list.insert(element, time))
+    }
+    /// Removes the element with the given [`Key`] and returns it
+    ///
+    /// Returns `None` if the element has timed out or was already removed.
+    pub fn remove(&self, key: Key) -> Option<T> {
+        self.with_list(|list| list.remove(key))
+    }
+    /// Gets the time left for the element with the given [`Key`]
+    ///
+    /// Returns `None` if the element has timed out or was already removed.
+    pub fn remaining(&self, key: Key) -> Option<Duration> {
+        self.shared.lock().list.remaining(key)
+    }
+    /// Gets the number of elements that have not timed out
+    pub fn len(&self) -> usize {
+        self.shared.lock().list.len()
+    }
+    /// Checks if there are no elements that have not timed out
+    pub fn is_empty(&self) -> bool {
+        self.shared.lock().list.is_empty()
+    }
+    /// Runs a function with mutable access to the underlying [`TimedList`]
+    ///
+    /// The background thread is woken afterwards so that any changes to expiration times take effect.
+    pub fn with_list<F, R>(&self, f: F) -> R
+    where
+        F: FnOnce(&mut TimedList<T>) -> R,
+    {
+        let res = f(&mut self.shared.lock().list);
+        self.shared.alarm.notify();
+        res
+    }
+}
+
+impl<T> Drop for ExpiryNotifier<T> {
+    fn drop(&mut self) {
+        self.shared.lock().stopped = true;
+        self.shared.alarm.notify();
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
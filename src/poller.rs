@@ -0,0 +1,119 @@
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap},
+    time::{Duration, Instant},
+};
+
+use crate::Timer;
+
+/// An identifier for a [`Timer`] registered with a [`Poller`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PollerId(u64);
+
+/**
+A registry of [`Timer`]s that reports which ones have become ready when polled
+
+Unlike a [`Scheduler`](crate::Scheduler), a [`Poller`] has no background thread.
+It is meant to be polled regularly, such as once per frame of a game loop.
+Polling only looks at the [`Timer`]s that are ready, so it stays cheap with many registered.
+
+# Example
+```
+use eggtimer::{Poller, Timer};
+
+let mut poller = Poller::new();
+let now = poller.register(Timer::set(0.0));
+let later = poller.register(Timer::set(60.0));
+assert_eq!(poller.poll(), vec![now]);
+assert!(poller.poll().is_empty());
+assert!(poller.contains(later));
+```
+*/
+#[derive(Debug, Clone, Default)]
+pub struct Poller {
+    queue: BinaryHeap<Reverse<(Instant, PollerId)>>,
+    timers: HashMap<PollerId, Timer>,
+    next_id: u64,
+}
+
+impl Poller {
+    /// Creates a new empty [`Poller`]
+    pub fn new() -> Poller {
+        Poller::default()
+    }
+    /// Registers a [`Timer`] and gets its [`PollerId`]
+    pub fn register(&mut self, timer: Timer) -> PollerId {
+        let id = PollerId(self.next_id);
+        self.next_id += 1;
+        self.queue.push(Reverse((timer.ends_at(), id)));
+        self.timers.insert(id, timer);
+        id
+    }
+    /// Removes a [`Timer`] before it becomes ready
+    pub fn remove(&mut self, id: PollerId) -> Option<Timer> {
+        let timer = self.timers.remove(&id)?;
+        self.purge();
+        Some(timer)
+    }
+    /// Gets a registered [`Timer`] that has not been reported as ready
+    pub fn get(&self, id: PollerId) -> Option<&Timer> {
+        self.timers.get(&id)
+    }
+    /// Checks if a [`Timer`] is registered and has not been reported as ready
+    pub fn contains(&self, id: PollerId) -> bool {
+        self.timers.contains_key(&id)
+    }
+    /// Gets the number of registered [`Timer`]s that have not been reported as ready
+    pub fn len(&self) -> usize {
+        self.timers.len()
+    }
+    /// Checks if there are no registered [`Timer`]s that have not been reported as ready
+    pub fn is_empty(&self) -> bool {
+        self.timers.is_empty()
+    }
+    /// Gets the [`PollerId`]s of the [`Timer`]s that have become ready since the last poll,
+    /// in the order they became ready
+    ///
+    /// Each [`Timer`] is reported once and then removed.
+    pub fn poll(&mut self) -> Vec<PollerId> {
+        let now = Instant::now();
+        let mut ready = Vec::new();
+        while let Some(&Reverse((deadline, id))) = self.queue.peek() {
+            if deadline > now {
+                break;
+            }
+            self.queue.pop();
+            if self.timers.remove(&id).is_some() {
+                ready.push(id);
+            }
+        }
+        ready
+    }
+    /// Gets the time until the next [`Timer`] becomes ready, or `None` if there are none
+    ///
+    /// This is zero if a [`Timer`] is already ready.
+    pub fn time_until_next(&mut self) -> Option<Duration> {
+        while let Some(&Reverse((deadline, id))) = self.queue.peek() {
+            if self.timers.contains_key(&id) {
+                return Some(deadline.saturating_duration_since(Instant::now()));
+            }
+            self.queue.pop();
+        }
+        None
+    }
+    /// Drops queued deadlines of removed [`Timer`]s from the front of the queue,
+    /// and rebuilds the queue once they outnumber the registered [`Timer`]s
+    fn purge(&mut self) {
+        while let Some(&Reverse((_, id))) = self.queue.peek() {
+            if self.timers.contains_key(&id) {
+                break;
+            }
+            self.queue.pop();
+        }
+        if self.queue.len() > 2 * self.timers.len() {
+            let timers = &self.timers;
+            self.queue
+                .retain(|Reverse((_, id))| timers.contains_key(id));
+        }
+    }
+}
@@ -0,0 +1,156 @@
+use std::{
+    collections::BTreeMap,
+    sync::{Arc, Mutex, MutexGuard, PoisonError},
+    time::Duration,
+};
+
+type Registry = Arc<Mutex<BTreeMap<&'static str, ProfileEntry>>>;
+
+/// The registries of every thread that has recorded a timing
+static THREADS: Mutex<Vec<Registry>> = Mutex::new(Vec::new());
+
+thread_local! {
+    /// This thread's registry
+    ///
+    /// Its lock is only ever contended while a report is being made or the registry is being reset.
+    static LOCAL: Registry = {
+        let registry = Registry::default();
+        lock(&THREADS).push(Arc::clone(&registry));
+        registry
+    };
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// The accumulated timings recorded for a label in the profiling registry
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ProfileEntry {
+    count: u64,
+    total: Duration,
+    min: Duration,
+    max: Duration,
+}
+
+impl ProfileEntry {
+    fn new(duration: Duration) -> ProfileEntry {
+        ProfileEntry {
+            count: 1,
+            total: duration,
+            min: duration,
+            max: duration,
+        }
+    }
+    fn record(&mut self, duration: Duration) {
+        self.count += 1;
+        self.total += duration;
+        self.min = self.min.min(duration);
+        self.max = self.max.max(duration);
+    }
+    /// Combines the timings of another [`ProfileEntry`] into this one
+    pub fn merge(&mut self, other: &ProfileEntry) {
+        self.count += other.count;
+        self.total += other.total;
+        self.min = self.min.min(other.min);
+        self.max = self.max.max(other.max);
+    }
+    /// Gets the number of times the label was recorded
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+    /// Gets the total recorded time
+    pub fn total(&self) -> Duration {
+        self.total
+    }
+    /// Gets the shortest recorded time
+    pub fn min(&self) -> Duration {
+        self.min
+    }
+    /// Gets the longest recorded time
+    pub fn max(&self) -> Duration {
+        self.max
+    }
+    /// Gets the mean recorded time
+    pub fn mean(&self) -> Duration {
+        Duration::from_nanos((self.total.as_nanos() / u128::from(self.count)) as u64)
+    }
+}
+
+/// Records the time until it is dropped if the `profiling` feature is enabled
+#[doc(hidden)]
+pub struct ProfileScope {
+    #[cfg(feature = "profiling")]
+    label: &'static str,
+    #[cfg(feature = "profiling")]
+    timer: crate::Elapsed,
+}
+
+impl ProfileScope {
+    #[inline]
+    #[cfg_attr(not(feature = "profiling"), allow(unused_variables))]
+    pub fn new(label: &'static str) -> ProfileScope {
+        ProfileScope {
+            #[cfg(feature = "profiling")]
+            label,
+            #[cfg(feature = "profiling")]
+            timer: crate::Elapsed::start(),
+        }
+    }
+}
+
+#[cfg(feature = "profiling")]
+impl Drop for ProfileScope {
+    fn drop(&mut self) {
+        profile_record(self.label, self.timer.duration());
+    }
+}
+
+/// Record a [`Duration`] for the given label in the profiling registry
+///
+/// Each thread records into its own registry, so recording does not contend
+/// with other threads. This is what the [`profile!`](crate::profile) macro calls.
+pub fn profile_record(label: &'static str, duration: Duration) {
+    LOCAL.with(|registry| {
+        lock(registry)
+            .entry(label)
+            .and_modify(|entry| entry.record(duration))
+            .or_insert_with(|| ProfileEntry::new(duration));
+    });
+}
+
+/// Get the timings recorded by all threads, merged and sorted by label
+///
+/// Timings recorded by threads that have since exited are included.
+pub fn profile_report() -> Vec<(&'static str, ProfileEntry)> {
+    let mut merged: BTreeMap<&'static str, ProfileEntry> = BTreeMap::new();
+    for registry in lock(&THREADS).iter() {
+        for (label, entry) in lock(registry).iter() {
+            merged
+                .entry(label)
+                .and_modify(|merged| merged.merge(entry))
+                .or_insert(*entry);
+        }
+    }
+    merged.into_iter().collect()
+}
+
+/// Get the timings recorded by the current thread, sorted by label
+pub fn profile_report_thread() -> Vec<(&'static str, ProfileEntry)> {
+    LOCAL.with(|registry| {
+        lock(registry)
+            .iter()
+            .map(|(label, entry)| (*label, *entry))
+            .collect()
+    })
+}
+
+/// Clear all timings recorded by all threads
+pub fn profile_reset() {
+    let mut threads = lock(&THREADS);
+    for registry in threads.iter() {
+        lock(registry).clear();
+    }
+    // Forget the registries of threads that have exited
+    threads.retain(|registry| Arc::strong_count(registry) > 1);
+}
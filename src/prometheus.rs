@@ -0,0 +1,132 @@
+use std::{
+    fmt::Write,
+    time::{Duration, Instant},
+};
+
+use crate::{Histogram, TimingStats, TokenBucket};
+
+/// The upper bounds in seconds of the buckets [`Histogram::render_prometheus`] renders,
+/// which are the Prometheus client libraries' defaults
+const DEFAULT_BUCKETS: [f64; 11] = [
+    0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0,
+];
+
+/// Formats labels as `{name="value",...}`, with an optional extra label
+fn format_labels(labels: &[(&str, &str)], extra: Option<(&str, &str)>) -> String {
+    let mut pairs = labels.iter().copied().chain(extra).peekable();
+    if pairs.peek().is_none() {
+        return String::new();
+    }
+    let mut s = String::from("{");
+    for (i, (name, value)) in pairs.enumerate() {
+        if i > 0 {
+            s.push(',');
+        }
+        s.push_str(name);
+        s.push_str("=\"");
+        for c in value.chars() {
+            match c {
+                '\\' => s.push_str("\\\\"),
+                '"' => s.push_str("\\\""),
+                '\n' => s.push_str("\\n"),
+                c => s.push(c),
+            }
+        }
+        s.push('"');
+    }
+    s.push('}');
+    s
+}
+
+impl TimingStats {
+    /// Renders the statistics in the Prometheus text exposition format
+    ///
+    /// The count and total are rendered as a summary named `name`, with times in seconds.
+    /// The minimum and maximum are rendered as gauges named `name_min` and `name_max`.
+    pub fn render_prometheus(&self, name: &str, labels: &[(&str, &str)]) -> String {
+        let l = format_labels(labels, None);
+        let mut s = String::new();
+        let _ = writeln!(s, "# TYPE {} summary", name);
+        let _ = writeln!(s, "{}_sum{} {}", name, l, self.total().as_secs_f64());
+        let _ = writeln!(s, "{}_count{} {}", name, l, self.count());
+        if let (Some(min), Some(max)) = (self.min(), self.max()) {
+            let _ = writeln!(s, "# TYPE {}_min gauge", name);
+            let _ = writeln!(s, "{}_min{} {}", name, l, min.as_secs_f64());
+            let _ = writeln!(s, "# TYPE {}_max gauge", name);
+            let _ = writeln!(s, "{}_max{} {}", name, l, max.as_secs_f64());
+        }
+        s
+    }
+}
+
+impl Histogram {
+    /// Renders the histogram in the Prometheus text exposition format
+    ///
+    /// The same cumulative `le` buckets are always rendered, with times in seconds, so that
+    /// every scrape has the same series. They are the Prometheus client libraries' defaults,
+    /// from 5 milliseconds to 10 seconds. Use [`Histogram::render_prometheus_with_bounds`]
+    /// to choose the buckets.
+    pub fn render_prometheus(&self, name: &str, labels: &[(&str, &str)]) -> String {
+        let bounds = DEFAULT_BUCKETS.map(Duration::from_secs_f64);
+        self.render_prometheus_with_bounds(name, labels, &bounds)
+    }
+    /// Renders the histogram in the Prometheus text exposition format with the given bucket upper bounds
+    ///
+    /// The bounds should be sorted from shortest to longest. A `+Inf` bucket is always added.
+    /// A recorded [`Duration`] is counted in a bucket if the upper bound of the histogram bucket
+    /// that holds it is within the `le` bound, so counts are only as precise as the histogram.
+    pub fn render_prometheus_with_bounds(
+        &self,
+        name: &str,
+        labels: &[(&str, &str)],
+        bounds: &[Duration],
+    ) -> String {
+        let mut s = String::new();
+        let _ = writeln!(s, "# TYPE {} histogram", name);
+        let buckets = self.buckets();
+        let mut cumulative = 0;
+        let mut i = 0;
+        for bound in bounds {
+            while let Some(&(upper, count)) = buckets.get(i) {
+                if upper > *bound {
+                    break;
+                }
+                cumulative += count;
+                i += 1;
+            }
+            let le = bound.as_secs_f64().to_string();
+            let l = format_labels(labels, Some(("le", &le)));
+            let _ = writeln!(s, "{}_bucket{} {}", name, l, cumulative);
+        }
+        let l = format_labels(labels, Some(("le", "+Inf")));
+        let _ = writeln!(s, "{}_bucket{} {}", name, l, self.count());
+        let l = format_labels(labels, None);
+        let _ = writeln!(s, "{}_sum{} {}", name, l, self.total().as_secs_f64());
+        let _ = writeln!(s, "{}_count{} {}", name, l, self.count());
+        s
+    }
+}
+
+impl TokenBucket {
+    /// Renders the bucket's rate in the Prometheus text exposition format
+    ///
+    /// The refill rate in tokens per second, the burst size, and the tokens currently
+    /// available are rendered as gauges named `name_rate`, `name_burst`, and `name_available`.
+    pub fn render_prometheus(&self, name: &str, labels: &[(&str, &str)]) -> String {
+        let l = format_labels(labels, None);
+        let mut s = String::new();
+        let _ = writeln!(s, "# TYPE {}_rate gauge", name);
+        let _ = writeln!(s, "{}_rate{} {}", name, l, self.rate());
+        let _ = writeln!(s, "# TYPE {}_burst gauge", name);
+        let _ = writeln!(s, "{}_burst{} {}", name, l, self.burst());
+        let _ = writeln!(s, "# TYPE {}_available gauge", name);
+        let _ = writeln!(
+            s,
+            "{}_available{} {}",
+            name,
+            l,
+            self.tokens_at(Instant::now())
+        );
+        s
+    }
+}
@@ -0,0 +1,222 @@
+use std::{
+    collections::VecDeque,
+    time::{Duration, Instant},
+};
+
+use crate::{Timer, ToDuration};
+
+/// A first-in-first-out queue where each element has an associated [`Duration`]
+///
+/// Elements are always iterated over and popped in the order they were pushed.
+/// Timed-out elements are never iterated over or popped. They are removed by [`TimedQueue::clean`],
+/// when they reach the front of the queue, and by [`TimedQueue::push_back`] when it would otherwise
+/// need to grow the queue and enough elements have been pushed since the last clean to pay for it.
+/// This way a queue that keeps getting new elements does not grow without bound.
+///
+/// Queues are compared by their elements that have not timed out and those elements' deadlines, from front to back.
+#[derive(Debug, Clone, Default)]
+pub struct TimedQueue<T> {
+    queue: VecDeque<(Timer, T)>,
+    /// The number of pushes since the queue was last cleaned
+    pushes: usize,
+}
+
+impl<T> TimedQueue<T> {
+    /// Creates a new [`TimedQueue`]
+    pub fn new() -> TimedQueue<T> {
+        TimedQueue {
+            queue: VecDeque::new(),
+            pushes: 0,
+        }
+    }
+    /// Pushes an element onto the back of the queue with the given number of floating-point seconds
+    pub fn push_back<D: ToDuration>(&mut self, element: T, time: D) {
+        // Growing is cheaper than a clean that frees little, so only clean once
+        // the pushes since the last clean number at least half the queue
+        if self.queue.len() == self.queue.capacity() && self.pushes >= self.queue.len() / 2 {
+            self.clean();
+        }
+        self.pushes += 1;
+        self.queue.push_back((Timer::set(time), element));
+    }
+    /// Removes the first element that has not timed out from the front of the queue and returns it
+    ///
+    /// Timed-out elements in front of it are dropped.
+    pub fn pop_front(&mut self) -> Option<T> {
+        while let Some((timer, elem)) = self.queue.pop_front() {
+            if !timer.is_ready() {
+                return Some(elem);
+            }
+        }
+        None
+    }
+    /// Gets a reference to the first element that has not timed out
+    pub fn front(&self) -> Option<&T> {
+        self.iter().next()
+    }
+    /// Gets a mutable reference to the first element that has not timed out
+    pub fn front_mut(&mut self) -> Option<&mut T> {
+        self.iter_mut().next()
+    }
+    /// Gets the time left for the first element that has not timed out
+    pub fn front_remaining(&self) -> Option<Duration> {
+        self.queue
+            .iter()
+            .find_map(|(timer, _)| timer.duration_left())
+    }
+    /// Forces the removal of all elements whose [`Duration`] has elpased.
+    pub fn clean(&mut self) {
+        let now = Instant::now();
+        self.queue.retain(|(timer, _)| timer.ends_at() >= now);
+        self.pushes = 0;
+    }
+    /// Removes all elements whose [`Duration`] has elapsed and returns them in queue order
+    pub fn take_expired(&mut self) -> Vec<T> {
+        let now = Instant::now();
+        let (expired, live): (VecDeque<_>, VecDeque<_>) = std::mem::take(&mut self.queue)
+            .into_iter()
+            .partition(|(timer, _)| timer.ends_at() < now);
+        self.queue = live;
+        expired.into_iter().map(|(_, elem)| elem).collect()
+    }
+    /// Removes all elements from the queue
+    pub fn clear(&mut self) {
+        self.queue.clear();
+    }
+    /// Gets the number of elements in the queue that have not timed out.
+    pub fn len(&self) -> usize {
+        self.iter().count()
+    }
+    /// Check if the queue is empty or if all existing elements have timed out.
+    pub fn is_empty(&self) -> bool {
+        self.iter().next().is_none()
+    }
+    /// Retains elements in the queue that match the predicate
+    pub fn retain<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&T) -> bool,
+    {
+        self.queue.retain(|(_, elem)| f(elem));
+    }
+    /// Iterates immutably through all elements from front to back.
+    ///
+    /// While this method does not remove timed-out elements,
+    /// it does filter them out.
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = &T> {
+        self.timer_iter().map(|(elem, _)| elem)
+    }
+    /// Iterates mutably through all elements from front to back.
+    pub fn iter_mut(&mut self) -> impl DoubleEndedIterator<Item = &mut T> {
+        self.clean();
+        self.queue.iter_mut().filter_map(
+            |(timer, elem)| {
+                if timer.is_ready() {
+                    None
+                } else {
+                    Some(elem)
+                }
+            },
+        )
+    }
+    /// Iterates immutably through all elements and their timers from front to back.
+    ///
+    /// While this method does not remove timed-out elements,
+    /// it does filter them out.
+    pub fn timer_iter(&self) -> impl DoubleEndedIterator<Item = (&T, Timer)> {
+        self.queue.iter().filter_map(|(timer, elem)| {
+            if timer.is_ready() {
+                None
+            } else {
+                Some((elem, *timer))
+            }
+        })
+    }
+    /// Iterates through the elements that have not timed out at the given [`Instant`] and their deadlines
+    fn live_entries(&self, now: Instant) -> impl Iterator<Item = (&T, Instant)> {
+        self.queue.iter().filter_map(move |(timer, elem)| {
+            let deadline = timer.ends_at();
+            if deadline < now {
+                None
+            } else {
+                Some((elem, deadline))
+            }
+        })
+    }
+}
+
+impl<T: PartialEq> PartialEq for TimedQueue<T> {
+    fn eq(&self, other: &Self) -> bool {
+        let now = Instant::now();
+        self.live_entries(now).eq(other.live_entries(now))
+    }
+}
+
+impl<T: PartialOrd> PartialOrd for TimedQueue<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        let now = Instant::now();
+        self.live_entries(now).partial_cmp(other.live_entries(now))
+    }
+}
+
+impl<T, D> std::iter::FromIterator<(T, D)> for TimedQueue<T>
+where
+    D: ToDuration,
+{
+    fn from_iter<I: IntoIterator<Item = (T, D)>>(iter: I) -> Self {
+        TimedQueue {
+            queue: iter.into_iter().map(|(x, d)| (Timer::set(d), x)).collect(),
+            pushes: 0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::thread;
+
+    use super::*;
+
+    #[test]
+    fn push_cleans_before_growing() {
+        let mut queue = TimedQueue::new();
+        for i in 0..10_000 {
+            queue.push_back(i, 0.0);
+        }
+        assert!(queue.queue.capacity() < 100);
+        assert!(queue.is_empty());
+    }
+
+    #[test]
+    fn small_cleans_let_the_queue_grow() {
+        let mut queue = TimedQueue::new();
+        let mut next = 0;
+        while queue.queue.is_empty() || queue.queue.len() + 1 < queue.queue.capacity() {
+            queue.push_back(next, 60.0);
+            next += 1;
+        }
+        queue.push_back(-1, 0.0);
+        thread::sleep(Duration::from_millis(1));
+        // The queue is full, so this cleans out the timed-out element
+        queue.push_back(next, 60.0);
+        assert!(queue.queue.iter().all(|(_, elem)| *elem != -1));
+        assert_eq!(queue.queue.len(), queue.queue.capacity());
+        // The last clean freed too little, so the queue grows instead of cleaning again
+        queue.push_back(-2, 0.0);
+        thread::sleep(Duration::from_millis(1));
+        queue.push_back(next + 1, 60.0);
+        assert!(queue.queue.iter().any(|(_, elem)| *elem == -2));
+    }
+
+    #[test]
+    fn equality_ignores_timed_out_elements() {
+        let mut a = TimedQueue::new();
+        a.push_back(1, 60.0);
+        let mut b = a.clone();
+        b.push_back(2, 0.0);
+        thread::sleep(Duration::from_millis(1));
+        assert_eq!(a, b);
+        b.push_back(3, 60.0);
+        assert_ne!(a, b);
+        assert!(a < b);
+    }
+}
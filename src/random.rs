@@ -0,0 +1,69 @@
+use std::time::Duration;
+
+use rand::Rng;
+
+use crate::{Timer, ToDuration};
+
+/**
+A range of durations that random durations can be sampled from
+
+# Example
+```
+use std::time::Duration;
+use eggtimer::DurationRange;
+
+let range = DurationRange::around(2.0, 0.5);
+let d = range.sample(&mut rand::thread_rng());
+assert!(d >= Duration::from_millis(1500) && d <= Duration::from_millis(2500));
+```
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DurationRange {
+    min: Duration,
+    max: Duration,
+}
+
+impl DurationRange {
+    /// Creates a new [`DurationRange`] between two numbers of floating-point seconds, inclusive
+    ///
+    /// The bounds may be given in either order.
+    pub fn new<A: ToDuration, B: ToDuration>(min: A, max: B) -> DurationRange {
+        let (a, b) = (min.to_duration(), max.to_duration());
+        DurationRange {
+            min: a.min(b),
+            max: a.max(b),
+        }
+    }
+    /// Creates a new [`DurationRange`] of `base` plus or minus `jitter`
+    ///
+    /// The lower bound saturates at zero.
+    pub fn around<B: ToDuration, J: ToDuration>(base: B, jitter: J) -> DurationRange {
+        let (base, jitter) = (base.to_duration(), jitter.to_duration());
+        DurationRange {
+            min: base.saturating_sub(jitter),
+            max: base.saturating_add(jitter),
+        }
+    }
+    /// Gets the lower bound
+    pub fn min(&self) -> Duration {
+        self.min
+    }
+    /// Gets the upper bound
+    pub fn max(&self) -> Duration {
+        self.max
+    }
+    /// Samples a uniformly random [`Duration`] from the range
+    pub fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Duration {
+        rng.gen_range(self.min..=self.max)
+    }
+}
+
+impl Timer {
+    /// Creates a new [`Timer`] set to `base` plus or minus a random amount up to `jitter`
+    ///
+    /// This uses the thread-local random number generator. The set [`Duration`] saturates at zero.
+    pub fn set_jittered<B: ToDuration, J: ToDuration>(base: B, jitter: J) -> Timer {
+        Timer::set(DurationRange::around(base, jitter).sample(&mut rand::thread_rng()))
+    }
+}
@@ -0,0 +1,423 @@
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap},
+    convert::TryFrom,
+    panic::{self, AssertUnwindSafe},
+    sync::{
+        atomic::{AtomicBool, AtomicI32, Ordering},
+        mpsc::{self, Receiver, SyncSender},
+        Arc, Mutex, MutexGuard, OnceLock, PoisonError,
+    },
+    thread::{self, JoinHandle},
+    time::{Duration, Instant},
+};
+
+use crate::{alarm::Alarm, ToDuration};
+
+type Task = Box<dyn FnMut() + Send>;
+
+/// The flags shared between a job and its [`JobHandle`]s
+#[derive(Default)]
+struct Flags {
+    paused: AtomicBool,
+    stopped: AtomicBool,
+    priority: AtomicI32,
+}
+
+struct Job {
+    /// The interval between runs, or `None` if the job only runs once
+    interval: Option<Duration>,
+    /// The task, which is taken out while it is running
+    task: Option<Task>,
+    flags: Arc<Flags>,
+}
+
+struct State {
+    queue: BinaryHeap<Reverse<(Instant, u64)>>,
+    jobs: HashMap<u64, Job>,
+    next_id: u64,
+    /// How far after the first due job other jobs may be due and still run in the same wakeup
+    coalesce: Duration,
+    stopped: bool,
+}
+
+struct Shared {
+    state: Mutex<State>,
+    alarm: Alarm,
+}
+
+impl Shared {
+    fn lock(&self) -> MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/**
+Runs jobs repeatedly on a background thread
+
+All of a [`Scheduler`]'s jobs share a single timing thread, which hands jobs that are due
+to a small pool of worker threads to run. Jobs are scheduled relative to when they were added,
+so they do not drift even if some runs are late. If a job falls more than a whole interval behind,
+the missed runs are skipped rather than run back-to-back.
+
+A job never runs concurrently with itself. If it is still running when its next run is due,
+that run is skipped. If all workers are busy and the hand-off queue is full, the timing thread
+waits for a worker to free up, so long-running jobs slow the [`Scheduler`] down instead of
+piling up.
+
+If a job panics, the panic is caught and the worker thread keeps running. A repeating job
+keeps its schedule and runs again at its next run, so a job that always panics keeps panicking.
+The panic is still reported by the panic hook, which prints it to standard error by default.
+
+Dropping the [`Scheduler`] stops all of its jobs and its background thread.
+
+# Example
+```
+use std::sync::{atomic::{AtomicUsize, Ordering}, Arc};
+use eggtimer::Scheduler;
+
+let scheduler = Scheduler::new();
+let count = Arc::new(AtomicUsize::new(0));
+let job_count = Arc::clone(&count);
+let job = scheduler.every(0.01, move || {
+    job_count.fetch_add(1, Ordering::Relaxed);
+});
+std::thread::sleep(std::time::Duration::from_millis(50));
+job.stop();
+assert!(count.load(Ordering::Relaxed) > 0);
+```
+*/
+pub struct Scheduler {
+    shared: Arc<Shared>,
+    threads: Vec<JoinHandle<()>>,
+}
+
+/// A handle to a job added to a [`Scheduler`]
+///
+/// Dropping the handle does not stop the job. A job that only runs once counts as stopped once it has been started.
+#[derive(Clone)]
+pub struct JobHandle {
+    id: u64,
+    flags: Arc<Flags>,
+    shared: Arc<Shared>,
+}
+
+impl Scheduler {
+    /// Creates a new [`Scheduler`] with a single worker thread
+    pub fn new() -> Scheduler {
+        Scheduler::with_workers(1)
+    }
+    /// Creates a new [`Scheduler`] with the given number of worker threads
+    ///
+    /// # Panics
+    ///
+    /// Panics if `workers` is 0
+    pub fn with_workers(workers: usize) -> Scheduler {
+        assert!(workers > 0, "Scheduler must have at least 1 worker");
+        let shared = Arc::new(Shared {
+            state: Mutex::new(State {
+                queue: BinaryHeap::new(),
+                jobs: HashMap::new(),
+                next_id: 0,
+                coalesce: Duration::from_secs(0),
+                stopped: false,
+            }),
+            alarm: Alarm::new(),
+        });
+        let (sender, receiver) = mpsc::sync_channel(workers);
+        let receiver = Arc::new(Mutex::new(receiver));
+        let mut threads: Vec<JoinHandle<()>> = (0..workers)
+            .map(|_| {
+                let shared = Arc::clone(&shared);
+                let receiver = Arc::clone(&receiver);
+                thread::spawn(move || work(&shared, &receiver))
+            })
+            .collect();
+        let thread_shared = Arc::clone(&shared);
+        threads.push(thread::spawn(move || run(&thread_shared, sender)));
+        Scheduler { shared, threads }
+    }
+    /**
+    Sets the coalescing window in floating-point seconds
+
+    When a job is due, any other jobs due within the window after it are run in the same
+    wakeup of the timing thread, so they may run up to the window early. This reduces how
+    often the timing thread wakes up when many jobs have close deadlines.
+    Within a wakeup, jobs are handed to the workers in order of [priority](JobHandle::set_priority).
+    Jobs keep their original schedules. The default window is zero.
+
+    # Example
+    ```
+    use eggtimer::Scheduler;
+
+    let scheduler = Scheduler::new().with_coalescing(0.005);
+    ```
+    */
+    pub fn with_coalescing<D: ToDuration>(self, window: D) -> Scheduler {
+        self.shared.lock().coalesce = window.to_duration();
+        self
+    }
+    /// Gets the global [`Scheduler`], creating it if this is the first use
+    ///
+    /// The global [`Scheduler`] has a single worker thread and is never dropped.
+    pub fn global() -> &'static Scheduler {
+        static GLOBAL: OnceLock<Scheduler> = OnceLock::new();
+        GLOBAL.get_or_init(Scheduler::new)
+    }
+    /// Runs a function every given number of floating-point seconds, starting one interval from now
+    ///
+    /// # Panics
+    ///
+    /// Panics if the interval is zero
+    pub fn every<D, F>(&self, interval: D, f: F) -> JobHandle
+    where
+        D: ToDuration,
+        F: FnMut() + Send + 'static,
+    {
+        let interval = interval.to_duration();
+        assert!(
+            interval > Duration::from_secs(0),
+            "Scheduler interval must not be zero"
+        );
+        self.add(Instant::now() + interval, Some(interval), Box::new(f))
+    }
+    /// Runs a function once after the given number of floating-point seconds
+    ///
+    /// If the job is paused when it is due, it is skipped and never runs.
+    pub fn after<D, F>(&self, delay: D, f: F) -> JobHandle
+    where
+        D: ToDuration,
+        F: FnOnce() + Send + 'static,
+    {
+        self.at(Instant::now() + delay.to_duration(), f)
+    }
+    /// Runs a function once at the given [`Instant`]
+    ///
+    /// If the job is paused when it is due, it is skipped and never runs.
+    pub fn at<F>(&self, deadline: Instant, f: F) -> JobHandle
+    where
+        F: FnOnce() + Send + 'static,
+    {
+        let mut f = Some(f);
+        self.add(
+            deadline,
+            None,
+            Box::new(move || {
+                if let Some(f) = f.take() {
+                    f()
+                }
+            }),
+        )
+    }
+    fn add(&self, deadline: Instant, interval: Option<Duration>, task: Task) -> JobHandle {
+        let flags = Arc::new(Flags::default());
+        let mut state = self.shared.lock();
+        let id = state.next_id;
+        state.next_id += 1;
+        state.jobs.insert(
+            id,
+            Job {
+                interval,
+                task: Some(task),
+                flags: Arc::clone(&flags),
+            },
+        );
+        state.queue.push(Reverse((deadline, id)));
+        drop(state);
+        self.shared.alarm.notify();
+        JobHandle {
+            id,
+            flags,
+            shared: Arc::clone(&self.shared),
+        }
+    }
+    /// Gets the number of jobs that have not been stopped
+    pub fn len(&self) -> usize {
+        self.shared.lock().jobs.len()
+    }
+    /// Checks if there are no jobs that have not been stopped
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl Default for Scheduler {
+    fn default() -> Self {
+        Scheduler::new()
+    }
+}
+
+impl Drop for Scheduler {
+    fn drop(&mut self) {
+        self.shared.lock().stopped = true;
+        self.shared.alarm.notify();
+        for thread in self.threads.drain(..) {
+            let _ = thread.join();
+        }
+    }
+}
+
+impl JobHandle {
+    /// Pauses the job
+    ///
+    /// Runs that would happen while the job is paused are skipped.
+    pub fn pause(&self) {
+        self.flags.paused.store(true, Ordering::Relaxed);
+    }
+    /// Resumes the job
+    ///
+    /// The job keeps its original schedule.
+    pub fn resume(&self) {
+        self.flags.paused.store(false, Ordering::Relaxed);
+    }
+    /// Checks if the job is paused
+    pub fn is_paused(&self) -> bool {
+        self.flags.paused.load(Ordering::Relaxed)
+    }
+    /// Stops the job permanently
+    ///
+    /// If the job is currently running, that run finishes.
+    pub fn stop(&self) {
+        self.flags.stopped.store(true, Ordering::Relaxed);
+        self.shared.lock().jobs.remove(&self.id);
+    }
+    /// Checks if the job has been stopped
+    pub fn is_stopped(&self) -> bool {
+        self.flags.stopped.load(Ordering::Relaxed)
+    }
+    /// Sets the job's priority
+    ///
+    /// When several jobs are due in the same wakeup, jobs with higher priorities are handed
+    /// to the workers first. This includes jobs that are overdue because the [`Scheduler`] is
+    /// falling behind, and jobs run early by [`Scheduler::with_coalescing`], so a coalescing
+    /// window also lets a higher-priority job overtake lower-priority jobs due slightly before it.
+    /// Jobs with equal priorities are handed over in deadline order. The default priority is 0.
+    pub fn set_priority(&self, priority: i32) {
+        self.flags.priority.store(priority, Ordering::Relaxed);
+    }
+    /// Gets the job's priority
+    pub fn priority(&self) -> i32 {
+        self.flags.priority.load(Ordering::Relaxed)
+    }
+}
+
+/// The timing thread's loop
+fn run(shared: &Shared, sender: SyncSender<(u64, Task)>) {
+    let mut state = shared.lock();
+    while !state.stopped {
+        let now = Instant::now();
+        match state.queue.peek() {
+            Some(&Reverse((deadline, _))) if deadline <= now => {
+                // Collect every job due within the coalescing window
+                let batch_end = now + state.coalesce;
+                let mut due = Vec::new();
+                while let Some(&Reverse((deadline, id))) = state.queue.peek() {
+                    if deadline > batch_end {
+                        break;
+                    }
+                    state.queue.pop();
+                    if let Some(job) = state.jobs.get(&id) {
+                        let priority = job.flags.priority.load(Ordering::Relaxed);
+                        due.push((Reverse(priority), deadline, id));
+                    }
+                }
+                due.sort_unstable();
+                let mut tasks = Vec::with_capacity(due.len());
+                for (_, deadline, id) in due {
+                    let job = match state.jobs.get_mut(&id) {
+                        Some(job) => job,
+                        None => continue,
+                    };
+                    let next = job
+                        .interval
+                        .map(|interval| next_run(deadline, interval, now));
+                    if !job.flags.paused.load(Ordering::Relaxed) {
+                        // The task is missing if the previous run is not finished
+                        if let Some(task) = job.task.take() {
+                            tasks.push((id, task));
+                        }
+                    }
+                    match next {
+                        Some(next) => state.queue.push(Reverse((next, id))),
+                        None => {
+                            if let Some(job) = state.jobs.remove(&id) {
+                                job.flags.stopped.store(true, Ordering::Relaxed);
+                            }
+                        }
+                    }
+                }
+                drop(state);
+                for task in tasks {
+                    if sender.send(task).is_err() {
+                        return;
+                    }
+                }
+                state = shared.lock();
+            }
+            Some(&Reverse((deadline, _))) => {
+                state = shared
+                    .alarm
+                    .wait(&shared.state, state, Some(deadline - now));
+            }
+            None => state = shared.alarm.wait(&shared.state, state, None),
+        }
+    }
+}
+
+/// A worker thread's loop
+fn work(shared: &Shared, receiver: &Mutex<Receiver<(u64, Task)>>) {
+    loop {
+        let received = receiver
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .recv();
+        let (id, mut task) = match received {
+            Ok(job) => job,
+            Err(_) => return,
+        };
+        // A panicking job must not take the worker down with it
+        let _ = panic::catch_unwind(AssertUnwindSafe(&mut task));
+        // The job may have been stopped while it was running
+        if let Some(job) = shared.lock().jobs.get_mut(&id) {
+            job.task = Some(task);
+        }
+    }
+}
+
+/// Gets the next run of a job after `now` on its original schedule
+fn next_run(last: Instant, interval: Duration, now: Instant) -> Instant {
+    let next = last + interval;
+    if next > now {
+        next
+    } else {
+        let interval = interval.as_nanos();
+        let behind = (now - next).as_nanos() / interval + 1;
+        next + Duration::from_nanos(u64::try_from(behind * interval).unwrap_or(u64::MAX))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::AtomicUsize;
+
+    use super::*;
+
+    #[test]
+    fn panicking_jobs_do_not_stop_others() {
+        let scheduler = Scheduler::new();
+        let panics = scheduler.every(0.005, || panic!("job panicked"));
+        let count = Arc::new(AtomicUsize::new(0));
+        let job_count = Arc::clone(&count);
+        let counter = scheduler.every(0.005, move || {
+            job_count.fetch_add(1, Ordering::Relaxed);
+        });
+        thread::sleep(Duration::from_millis(100));
+        let before = count.load(Ordering::Relaxed);
+        thread::sleep(Duration::from_millis(50));
+        assert!(before > 1);
+        assert!(count.load(Ordering::Relaxed) > before);
+        assert!(!panics.is_stopped());
+        counter.stop();
+        panics.stop();
+    }
+}
@@ -0,0 +1,81 @@
+use std::time::Duration;
+
+use crate::{Elapsed, ToDuration};
+
+/// Where a [`ScopeTimer`] reports the elapsed time
+#[derive(Debug, Clone, Copy)]
+enum Sink {
+    Stderr,
+    #[cfg(feature = "log")]
+    Log(log::Level),
+}
+
+/**
+A timer that reports how long it existed when it is dropped
+
+This makes it easy to time a whole scope, including early returns.
+
+# Example
+```
+use eggtimer::ScopeTimer;
+
+fn load() {
+    let _timer = ScopeTimer::new("load").with_threshold(0.5);
+    // Do some work. If it takes longer than half a second,
+    // "load took ..." is printed to standard error.
+}
+```
+*/
+#[derive(Debug)]
+pub struct ScopeTimer {
+    label: String,
+    timer: Elapsed,
+    threshold: Duration,
+    sink: Sink,
+}
+
+impl ScopeTimer {
+    /// Creates a new [`ScopeTimer`] that prints the elapsed time to standard error when dropped
+    pub fn new<S: Into<String>>(label: S) -> ScopeTimer {
+        ScopeTimer {
+            label: label.into(),
+            timer: Elapsed::start(),
+            threshold: Duration::from_secs(0),
+            sink: Sink::Stderr,
+        }
+    }
+    /// Creates a new [`ScopeTimer`] that logs the elapsed time at the given level through the `log` crate when dropped
+    #[cfg(feature = "log")]
+    pub fn log<S: Into<String>>(level: log::Level, label: S) -> ScopeTimer {
+        let mut timer = ScopeTimer::new(label);
+        timer.sink = Sink::Log(level);
+        timer
+    }
+    /// Only report the elapsed time if it is longer than the given number of floating-point seconds
+    pub fn with_threshold<D: ToDuration>(mut self, threshold: D) -> ScopeTimer {
+        self.threshold = threshold.to_duration();
+        self
+    }
+    /// Gets the label
+    pub fn label(&self) -> &str {
+        &self.label
+    }
+    /// Gets the time elapsed since the [`ScopeTimer`] was created
+    pub fn elapsed(&self) -> Duration {
+        self.timer.duration()
+    }
+}
+
+impl Drop for ScopeTimer {
+    fn drop(&mut self) {
+        let elapsed = self.elapsed();
+        if elapsed < self.threshold {
+            return;
+        }
+        match self.sink {
+            Sink::Stderr => eprintln!("{} took {:?}", self.label, elapsed),
+            #[cfg(feature = "log")]
+            Sink::Log(level) => log::log!(level, "{} took {:?}", self.label, elapsed),
+        }
+    }
+}
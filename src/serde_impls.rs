@@ -0,0 +1,109 @@
+use std::{collections::HashSet, time::Duration};
+
+use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{CleanPolicy, Entry, Eviction, Key, ListOrder, TimedList, Timer};
+
+#[derive(Serialize)]
+struct SerTimedList<'a, T> {
+    order: ListOrder,
+    bound: Option<(usize, Eviction)>,
+    clean_policy: CleanPolicy,
+    next_key: u64,
+    entries: Vec<SerEntry<'a, T>>,
+}
+
+#[derive(Serialize)]
+struct SerEntry<'a, T> {
+    key: Key,
+    elem: &'a T,
+    remaining: Duration,
+}
+
+#[derive(Deserialize)]
+struct DeTimedList<T> {
+    order: ListOrder,
+    #[serde(default)]
+    bound: Option<(usize, Eviction)>,
+    #[serde(default)]
+    clean_policy: CleanPolicy,
+    next_key: u64,
+    entries: Vec<DeEntry<T>>,
+}
+
+#[derive(Deserialize)]
+struct DeEntry<T> {
+    key: Key,
+    elem: T,
+    remaining: Duration,
+}
+
+/// Elements are serialized with the time they have left rather than the [`Instant`](std::time::Instant)
+/// at which they were inserted, so a deserialized [`TimedList`] resumes each element's countdown.
+/// The [`ListOrder`], bound, and [`CleanPolicy`] are preserved.
+/// Timed-out elements and the expiration callback are not serialized.
+impl<T> Serialize for TimedList<T>
+where
+    T: Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let entries = self
+            .list
+            .iter()
+            .filter_map(|entry| {
+                entry.timer.duration_left().map(|remaining| SerEntry {
+                    key: entry.key,
+                    elem: &entry.elem,
+                    remaining,
+                })
+            })
+            .collect();
+        SerTimedList {
+            order: self.order,
+            bound: self.bound,
+            clean_policy: self.clean_policy,
+            next_key: self.next_key,
+            entries,
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de, T> Deserialize<'de> for TimedList<T>
+where
+    T: Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let de = DeTimedList::deserialize(deserializer)?;
+        let mut list = TimedList::with_order(de.order);
+        list.bound = de.bound;
+        list.clean_policy = de.clean_policy;
+        list.reserve(de.entries.len());
+        let mut next_key = de.next_key;
+        let mut keys = HashSet::with_capacity(de.entries.len());
+        for entry in de.entries {
+            // Keys handed out later must not alias the deserialized ones
+            if !keys.insert(entry.key) {
+                return Err(D::Error::custom(format_args!(
+                    "duplicate TimedList key {}",
+                    entry.key.0
+                )));
+            }
+            next_key = next_key.max(entry.key.0.saturating_add(1));
+            let entry = Entry {
+                key: entry.key,
+                timer: Timer::set(entry.remaining),
+                elem: entry.elem,
+            };
+            list.place_entry(entry, list.list.len());
+        }
+        list.next_key = next_key;
+        Ok(list)
+    }
+}
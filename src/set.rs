@@ -0,0 +1,198 @@
+use std::{
+    borrow::Borrow,
+    collections::HashMap,
+    hash::Hash,
+    time::{Duration, Instant},
+};
+
+use crate::{Timer, ToDuration};
+
+/// A set where each element has an associated [`Duration`]
+///
+/// Inserting an element that is already in the set refreshes its [`Duration`]
+/// instead of adding a duplicate. Timed-out elements are never iterated over.
+/// They are removed by [`TimedSet::clean`], and by [`TimedSet::insert`] when it would otherwise
+/// need to grow the set and enough elements have been inserted since the last clean to pay for it.
+/// This way a set that keeps getting new elements does not grow without bound.
+#[derive(Debug, Clone, Default)]
+pub struct TimedSet<T> {
+    map: HashMap<T, Timer>,
+    /// The number of insertions since the set was last cleaned
+    inserts: usize,
+}
+
+impl<T> TimedSet<T>
+where
+    T: Hash + Eq,
+{
+    /// Creates a new [`TimedSet`]
+    pub fn new() -> TimedSet<T> {
+        TimedSet {
+            map: HashMap::new(),
+            inserts: 0,
+        }
+    }
+    /// Inserts an element into the set with the given number of floating-point seconds
+    ///
+    /// If the element is already in the set, its countdown is restarted with the new [`Duration`].
+    /// Returns `true` if the element was not already in the set or had timed out.
+    pub fn insert<D: ToDuration>(&mut self, element: T, time: D) -> bool {
+        // A clean visits every element, so it is only worth doing before the map grows,
+        // and only once there have been at least half as many insertions as elements since the last one
+        if self.map.len() == self.map.capacity()
+            && self.inserts >= self.map.len() / 2
+            && !self.map.contains_key(&element)
+        {
+            self.clean();
+        }
+        self.inserts += 1;
+        match self.map.insert(element, Timer::set(time)) {
+            Some(timer) => timer.is_ready(),
+            None => true,
+        }
+    }
+    /// Checks if the set contains an element that has not timed out
+    pub fn contains<Q>(&self, element: &Q) -> bool
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.remaining(element).is_some()
+    }
+    /// Restarts the countdown of the element from its original [`Duration`]
+    ///
+    /// Returns `false` if the element has timed out or is not in the set.
+    pub fn touch<Q>(&mut self, element: &Q) -> bool
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        match self.map.get_mut(element) {
+            Some(timer) if !timer.is_ready() => {
+                timer.reset();
+                true
+            }
+            _ => false,
+        }
+    }
+    /// Removes the element from the set
+    ///
+    /// Returns `false` if the element has timed out or is not in the set.
+    pub fn remove<Q>(&mut self, element: &Q) -> bool
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.map
+            .remove(element)
+            .is_some_and(|timer| !timer.is_ready())
+    }
+    /// Gets the time left for the element
+    ///
+    /// Returns `None` if the element has timed out or is not in the set.
+    pub fn remaining<Q>(&self, element: &Q) -> Option<Duration>
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.map.get(element).and_then(Timer::duration_left)
+    }
+    /// Forces the removal of all elements whose [`Duration`] has elpased.
+    pub fn clean(&mut self) {
+        let now = Instant::now();
+        self.map.retain(|_, timer| timer.ends_at() >= now);
+        self.inserts = 0;
+    }
+    /// Removes all elements from the set
+    pub fn clear(&mut self) {
+        self.map.clear();
+    }
+    /// Gets the number of elements in the set that have not timed out.
+    pub fn len(&self) -> usize {
+        self.iter().count()
+    }
+    /// Check if the set is empty or if all existing elements have timed out.
+    pub fn is_empty(&self) -> bool {
+        self.iter().next().is_none()
+    }
+    /// Retains elements in the set that match the predicate
+    pub fn retain<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&T) -> bool,
+    {
+        self.map.retain(|elem, _| f(elem));
+    }
+    /// Iterates through all elements in an arbitrary order.
+    ///
+    /// While this method does not remove timed-out elements,
+    /// it does filter them out.
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.timer_iter().map(|(elem, _)| elem)
+    }
+    /// Iterates through all elements and their timers in an arbitrary order.
+    ///
+    /// While this method does not remove timed-out elements,
+    /// it does filter them out.
+    pub fn timer_iter(&self) -> impl Iterator<Item = (&T, Timer)> {
+        self.map.iter().filter_map(|(elem, timer)| {
+            if timer.is_ready() {
+                None
+            } else {
+                Some((elem, *timer))
+            }
+        })
+    }
+}
+
+impl<T, D> std::iter::FromIterator<(T, D)> for TimedSet<T>
+where
+    T: Hash + Eq,
+    D: ToDuration,
+{
+    fn from_iter<I: IntoIterator<Item = (T, D)>>(iter: I) -> Self {
+        let mut set = TimedSet::new();
+        for (x, d) in iter {
+            set.insert(x, d);
+        }
+        set
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::thread;
+
+    use super::*;
+
+    #[test]
+    fn insert_cleans_before_growing() {
+        let mut set = TimedSet::new();
+        for i in 0..10_000 {
+            set.insert(i, 0.0);
+        }
+        assert!(set.map.capacity() < 100);
+        assert!(set.is_empty());
+    }
+
+    #[test]
+    fn small_cleans_let_the_set_grow() {
+        let mut set = TimedSet::new();
+        let mut next = 0;
+        while set.map.is_empty() || set.map.len() + 1 < set.map.capacity() {
+            set.insert(next, 60.0);
+            next += 1;
+        }
+        set.insert(-1, 0.0);
+        thread::sleep(Duration::from_millis(1));
+        // The set is full, so this cleans out the timed-out element
+        set.insert(next, 60.0);
+        assert!(!set.map.contains_key(&-1));
+        assert_eq!(set.map.len(), set.map.capacity());
+        // The last clean freed too little, so the set grows instead of cleaning again
+        set.insert(-2, 0.0);
+        thread::sleep(Duration::from_millis(1));
+        set.insert(next + 1, 60.0);
+        assert!(set.map.contains_key(&-2));
+        assert_eq!(set.len(), next as usize + 2);
+    }
+}
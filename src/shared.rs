@@ -0,0 +1,336 @@
+use std::{
+    collections::BTreeMap,
+    convert::TryFrom,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex, MutexGuard, PoisonError, RwLock,
+    },
+    time::{Duration, Instant},
+};
+
+use crate::{FromDuration, Seconds, Timer, ToDuration};
+
+/// The bit of an [`AtomicStopwatch`]'s state that is set while it is paused
+const PAUSED: u64 = 1 << 63;
+/// The bits of an [`AtomicStopwatch`]'s state that hold nanoseconds
+const NANOS: u64 = PAUSED - 1;
+
+/**
+A [`Stopwatch`](crate::Stopwatch) whose state is stored in a single atomic
+
+An [`AtomicStopwatch`] can be shared between threads without a [`Mutex`](std::sync::Mutex).
+Any thread can read the elapsed time or pause, resume, or reset it.
+Reads are a single atomic load, and changes are a compare-and-swap that only retries
+if another thread changed the stopwatch at the same time, so no thread ever waits on another.
+
+# Example
+```
+use std::sync::Arc;
+use eggtimer::AtomicStopwatch;
+
+let uptime = Arc::new(AtomicStopwatch::start());
+let reader = Arc::clone(&uptime);
+std::thread::spawn(move || println!("{:?}", reader.duration()))
+    .join()
+    .unwrap();
+uptime.pause();
+assert!(uptime.is_paused());
+```
+*/
+#[derive(Debug)]
+pub struct AtomicStopwatch {
+    epoch: Instant,
+    /// While paused, the [`PAUSED`] bit plus the elapsed nanoseconds.
+    /// While running, the nanoseconds since the epoch at which it would have
+    /// started to have the current elapsed time.
+    state: AtomicU64,
+}
+
+impl AtomicStopwatch {
+    /// Creates a new [`AtomicStopwatch`] which immediately starts counting
+    pub fn start() -> AtomicStopwatch {
+        AtomicStopwatch {
+            epoch: Instant::now(),
+            state: AtomicU64::new(0),
+        }
+    }
+    /// Creates a new [`AtomicStopwatch`] which starts paused
+    pub fn start_paused() -> AtomicStopwatch {
+        AtomicStopwatch {
+            epoch: Instant::now(),
+            state: AtomicU64::new(PAUSED),
+        }
+    }
+    /// Gets the elapsed time as a [`Duration`]
+    pub fn duration(&self) -> Duration {
+        let state = self.state.load(Ordering::Acquire);
+        let nanos = if state & PAUSED != 0 {
+            state & NANOS
+        } else {
+            self.now().saturating_sub(state)
+        };
+        Duration::from_nanos(nanos)
+    }
+    /// Gets the elapsed time as a floating-point number of seconds
+    pub fn seconds(&self) -> Seconds {
+        Seconds::from_duration(self.duration())
+    }
+    /// Checks if the [`AtomicStopwatch`] is paused
+    pub fn is_paused(&self) -> bool {
+        self.state.load(Ordering::Acquire) & PAUSED != 0
+    }
+    /// Pauses the [`AtomicStopwatch`]
+    pub fn pause(&self) {
+        self.update(|state, now| {
+            if state & PAUSED != 0 {
+                state
+            } else {
+                PAUSED | now.saturating_sub(state)
+            }
+        });
+    }
+    /// Resumes the [`AtomicStopwatch`]
+    pub fn resume(&self) {
+        self.update(|state, now| {
+            if state & PAUSED != 0 {
+                now.saturating_sub(state & NANOS)
+            } else {
+                state
+            }
+        });
+    }
+    /// Restarts the [`AtomicStopwatch`] without pausing or resuming
+    pub fn reset(&self) {
+        self.update(|state, now| if state & PAUSED != 0 { PAUSED } else { now });
+    }
+    /// Gets the nanoseconds since the epoch
+    fn now(&self) -> u64 {
+        u64::try_from(self.epoch.elapsed().as_nanos())
+            .unwrap_or(NANOS)
+            .min(NANOS)
+    }
+    /// Changes the state with a compare-and-swap loop
+    fn update<F>(&self, mut f: F)
+    where
+        F: FnMut(u64, u64) -> u64,
+    {
+        let _ = self
+            .state
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |state| {
+                Some(f(state, self.now()))
+            });
+    }
+}
+
+impl Default for AtomicStopwatch {
+    fn default() -> Self {
+        AtomicStopwatch::start()
+    }
+}
+
+/**
+A cheap-to-clone [`Timer`] that can be reset by one holder and read by many
+
+Clones share the same [`Timer`]. Every method works on a consistent snapshot,
+and once a method that changes the [`Timer`] returns, every clone sees the change.
+Use [`SharedTimer::get`] to take a snapshot when several readings need to agree with each other.
+
+# Example
+```
+use eggtimer::SharedTimer;
+
+let timeout = SharedTimer::new(30.0);
+let watcher = timeout.clone();
+timeout.reset();
+assert!(!watcher.is_ready());
+```
+*/
+#[derive(Debug, Clone, Default)]
+pub struct SharedTimer {
+    timer: Arc<RwLock<Timer>>,
+}
+
+impl SharedTimer {
+    /// Creates a new [`SharedTimer`] set to the given number of floating-point seconds
+    pub fn new<D: ToDuration>(time: D) -> SharedTimer {
+        SharedTimer::from(Timer::set(time))
+    }
+    /// Gets a snapshot of the [`Timer`]
+    pub fn get(&self) -> Timer {
+        *self.timer.read().unwrap_or_else(PoisonError::into_inner)
+    }
+    /// Replaces the [`Timer`] with one set to the given number of floating-point seconds
+    pub fn set<D: ToDuration>(&self, time: D) {
+        self.with(|timer| *timer = Timer::set(time));
+    }
+    /// Resets the [`Timer`]
+    pub fn reset(&self) {
+        self.with(Timer::reset);
+    }
+    /// Runs a function with mutable access to the [`Timer`]
+    ///
+    /// Readers wait until the function returns.
+    pub fn with<F, R>(&self, f: F) -> R
+    where
+        F: FnOnce(&mut Timer) -> R,
+    {
+        f(&mut self.timer.write().unwrap_or_else(PoisonError::into_inner))
+    }
+    /// Checks if the set [`Duration`] has elapsed
+    pub fn is_ready(&self) -> bool {
+        self.get().is_ready()
+    }
+    /// Gets the time left as a [`Duration`]
+    pub fn duration_left(&self) -> Option<Duration> {
+        self.get().duration_left()
+    }
+    /// Gets the time left as a floating-point number of seconds
+    pub fn seconds_left(&self) -> Seconds {
+        self.get().seconds_left()
+    }
+}
+
+impl From<Timer> for SharedTimer {
+    fn from(timer: Timer) -> Self {
+        SharedTimer {
+            timer: Arc::new(RwLock::new(timer)),
+        }
+    }
+}
+
+/**
+A global stopwatch that runs while any thread is inside a section of the work it tracks
+
+This is created by [`stopwatch`]. Every [`NamedStopwatch::resume`] starts a section and
+every [`NamedStopwatch::pause`] ends one. The stopwatch starts when the first section starts
+and pauses when the last one ends, so overlapping sections on different threads are neither
+cut short nor counted twice. The elapsed time is the wall-clock time spent in the work.
+*/
+#[derive(Debug)]
+pub struct NamedStopwatch {
+    stopwatch: AtomicStopwatch,
+    /// The number of sections that have started but not ended
+    active: Mutex<usize>,
+}
+
+/// A section of a [`NamedStopwatch`] that ends when it is dropped
+///
+/// This is created by [`NamedStopwatch::section`].
+#[derive(Debug)]
+#[must_use = "the section ends as soon as the guard is dropped"]
+pub struct SectionGuard {
+    stopwatch: Arc<NamedStopwatch>,
+}
+
+impl NamedStopwatch {
+    fn new() -> NamedStopwatch {
+        NamedStopwatch {
+            stopwatch: AtomicStopwatch::start_paused(),
+            active: Mutex::new(0),
+        }
+    }
+    fn active(&self) -> MutexGuard<'_, usize> {
+        self.active.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+    /// Starts a section, resuming the stopwatch if no other section is running
+    pub fn resume(&self) {
+        let mut active = self.active();
+        if *active == 0 {
+            self.stopwatch.resume();
+        }
+        *active += 1;
+    }
+    /// Ends a section, pausing the stopwatch if no other section is running
+    ///
+    /// Does nothing if no section is running.
+    pub fn pause(&self) {
+        let mut active = self.active();
+        match *active {
+            0 => {}
+            1 => {
+                self.stopwatch.pause();
+                *active = 0;
+            }
+            _ => *active -= 1,
+        }
+    }
+    /// Starts a section that ends when the returned guard is dropped
+    pub fn section(self: &Arc<Self>) -> SectionGuard {
+        self.resume();
+        SectionGuard {
+            stopwatch: Arc::clone(self),
+        }
+    }
+    /// Gets the number of sections that are running
+    pub fn sections(&self) -> usize {
+        *self.active()
+    }
+    /// Checks if no section is running
+    pub fn is_paused(&self) -> bool {
+        self.stopwatch.is_paused()
+    }
+    /// Gets the elapsed time as a [`Duration`]
+    pub fn duration(&self) -> Duration {
+        self.stopwatch.duration()
+    }
+    /// Gets the elapsed time as a floating-point number of seconds
+    pub fn seconds(&self) -> Seconds {
+        self.stopwatch.seconds()
+    }
+}
+
+impl Drop for SectionGuard {
+    fn drop(&mut self) {
+        self.stopwatch.pause();
+    }
+}
+
+/// The global [`NamedStopwatch`]es
+static STOPWATCHES: Mutex<BTreeMap<String, Arc<NamedStopwatch>>> = Mutex::new(BTreeMap::new());
+
+fn stopwatches_lock() -> MutexGuard<'static, BTreeMap<String, Arc<NamedStopwatch>>> {
+    STOPWATCHES.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/**
+Get the global [`NamedStopwatch`] with the given name, creating it if this is the first use
+
+A newly created stopwatch is paused. Start a section when entering the work being tracked
+and end it when leaving, and it accumulates the total time spent in that work
+across the whole program. Every call with the same name returns the same stopwatch.
+
+# Example
+```
+use eggtimer::{stopwatch, stopwatch_report};
+
+fn query() {
+    let _db = stopwatch("db").section();
+    // ...
+}
+
+let threads: Vec<_> = (0..4).map(|_| std::thread::spawn(query)).collect();
+for thread in threads {
+    thread.join().unwrap();
+}
+for (name, time) in stopwatch_report() {
+    println!("{}: {:?}", name, time);
+}
+```
+*/
+pub fn stopwatch(name: &str) -> Arc<NamedStopwatch> {
+    let mut stopwatches = stopwatches_lock();
+    if let Some(stopwatch) = stopwatches.get(name) {
+        return Arc::clone(stopwatch);
+    }
+    let stopwatch = Arc::new(NamedStopwatch::new());
+    stopwatches.insert(name.into(), Arc::clone(&stopwatch));
+    stopwatch
+}
+
+/// Get the name and elapsed time of every global [`NamedStopwatch`] created by [`stopwatch`], sorted by name
+pub fn stopwatch_report() -> Vec<(String, Duration)> {
+    stopwatches_lock()
+        .iter()
+        .map(|(name, stopwatch)| (name.clone(), stopwatch.duration()))
+        .collect()
+}
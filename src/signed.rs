@@ -0,0 +1,178 @@
+use std::{
+    cmp::Ordering,
+    fmt,
+    ops::{Add, Neg, Sub},
+    time::{Duration, Instant},
+};
+
+use crate::{FromDuration, NegativeDuration, TryToDuration};
+
+/**
+A [`Duration`] that can be negative
+
+This is useful for differences between times, overdue amounts, and drift,
+where an unsigned [`Duration`] would need to be wrapped in an [`Option`].
+
+# Example
+```
+use std::time::Duration;
+use eggtimer::SignedDuration;
+
+let a = SignedDuration::from(Duration::from_secs(1));
+let b = SignedDuration::from(Duration::from_secs(3));
+assert_eq!(a - b, -SignedDuration::from(Duration::from_secs(2)));
+assert!((a - b).is_negative());
+```
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct SignedDuration {
+    negative: bool,
+    magnitude: Duration,
+}
+
+impl SignedDuration {
+    /// A zero [`SignedDuration`]
+    pub const ZERO: SignedDuration = SignedDuration {
+        negative: false,
+        magnitude: Duration::from_secs(0),
+    };
+    /// Creates a new [`SignedDuration`] from a sign and a magnitude
+    pub fn new(negative: bool, magnitude: Duration) -> SignedDuration {
+        SignedDuration {
+            negative: negative && magnitude != Duration::from_secs(0),
+            magnitude,
+        }
+    }
+    /// Gets the signed time from `earlier` to `later`
+    ///
+    /// The result is negative if `later` is actually earlier.
+    pub fn between(earlier: Instant, later: Instant) -> SignedDuration {
+        if later >= earlier {
+            SignedDuration::new(false, later - earlier)
+        } else {
+            SignedDuration::new(true, earlier - later)
+        }
+    }
+    /// Checks if the [`SignedDuration`] is less than zero
+    pub fn is_negative(&self) -> bool {
+        self.negative
+    }
+    /// Checks if the [`SignedDuration`] is greater than zero
+    pub fn is_positive(&self) -> bool {
+        !self.negative && self.magnitude != Duration::from_secs(0)
+    }
+    /// Gets the magnitude, ignoring the sign
+    pub fn abs(&self) -> Duration {
+        self.magnitude
+    }
+    /// Gets the [`Duration`] if the [`SignedDuration`] is not negative
+    pub fn positive(&self) -> Option<Duration> {
+        if self.negative {
+            None
+        } else {
+            Some(self.magnitude)
+        }
+    }
+    /// Gets the [`SignedDuration`] as a signed floating-point number of seconds
+    pub fn as_secs_f64(&self) -> f64 {
+        let secs = self.magnitude.as_secs_f64();
+        if self.negative {
+            -secs
+        } else {
+            secs
+        }
+    }
+    /// Creates a [`SignedDuration`] from a signed floating-point number of seconds
+    ///
+    /// # Panics
+    ///
+    /// Panics if `secs` is not finite or is too large
+    pub fn from_secs_f64(secs: f64) -> SignedDuration {
+        SignedDuration::new(secs < 0.0, Duration::from_secs_f64(secs.abs()))
+    }
+}
+
+impl From<Duration> for SignedDuration {
+    fn from(duration: Duration) -> Self {
+        SignedDuration::new(false, duration)
+    }
+}
+
+impl FromDuration for SignedDuration {
+    fn from_duration(duration: Duration) -> Self {
+        SignedDuration::from(duration)
+    }
+}
+
+impl TryToDuration for SignedDuration {
+    fn try_to_duration(&self) -> Result<Duration, NegativeDuration> {
+        self.positive().ok_or(NegativeDuration)
+    }
+}
+
+impl Ord for SignedDuration {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self.negative, other.negative) {
+            (false, false) => self.magnitude.cmp(&other.magnitude),
+            (true, true) => other.magnitude.cmp(&self.magnitude),
+            (false, true) => Ordering::Greater,
+            (true, false) => Ordering::Less,
+        }
+    }
+}
+
+impl PartialOrd for SignedDuration {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Neg for SignedDuration {
+    type Output = SignedDuration;
+    fn neg(self) -> SignedDuration {
+        SignedDuration::new(!self.negative, self.magnitude)
+    }
+}
+
+impl Add for SignedDuration {
+    type Output = SignedDuration;
+    fn add(self, other: SignedDuration) -> SignedDuration {
+        if self.negative == other.negative {
+            SignedDuration::new(self.negative, self.magnitude + other.magnitude)
+        } else if self.magnitude >= other.magnitude {
+            SignedDuration::new(self.negative, self.magnitude - other.magnitude)
+        } else {
+            SignedDuration::new(other.negative, other.magnitude - self.magnitude)
+        }
+    }
+}
+
+impl Sub for SignedDuration {
+    type Output = SignedDuration;
+    fn sub(self, other: SignedDuration) -> SignedDuration {
+        self + -other
+    }
+}
+
+impl Add<Duration> for SignedDuration {
+    type Output = SignedDuration;
+    fn add(self, other: Duration) -> SignedDuration {
+        self + SignedDuration::from(other)
+    }
+}
+
+impl Sub<Duration> for SignedDuration {
+    type Output = SignedDuration;
+    fn sub(self, other: Duration) -> SignedDuration {
+        self - SignedDuration::from(other)
+    }
+}
+
+impl fmt::Display for SignedDuration {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.negative {
+            write!(f, "-")?;
+        }
+        write!(f, "{:?}", self.magnitude)
+    }
+}
@@ -0,0 +1,122 @@
+use std::{
+    hint, thread,
+    time::{Duration, Instant},
+};
+
+use crate::{Ticker, Timer, ToDuration};
+
+/// The number of sleeps [`SpinSleeper::calibrated`] measures
+const CALIBRATION_SAMPLES: u32 = 20;
+
+/**
+Sleeps precisely by sleeping coarsely, then spinning for the final stretch
+
+The OS may wake a sleeping thread late by up to its native sleep accuracy.
+A [`SpinSleeper`] sleeps until that much time is left, then busy-waits until the deadline.
+This hits deadlines to well under a millisecond, at the cost of keeping a CPU core busy
+for up to the native sleep accuracy on every sleep.
+
+# Example
+```
+use eggtimer::{SpinSleeper, Ticker};
+
+let sleeper = SpinSleeper::calibrated();
+let mut frames = Ticker::new(1.0 / 240.0);
+for _ in 0..3 {
+    frames.wait_with(&sleeper);
+    // Render a frame
+}
+```
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SpinSleeper {
+    native_accuracy: Duration,
+}
+
+impl SpinSleeper {
+    /// Creates a new [`SpinSleeper`] that spins for the given number of floating-point seconds
+    /// at the end of each sleep
+    pub fn new<D: ToDuration>(native_accuracy: D) -> SpinSleeper {
+        SpinSleeper {
+            native_accuracy: native_accuracy.to_duration(),
+        }
+    }
+    /// Creates a new [`SpinSleeper`] whose native sleep accuracy is measured by sleeping briefly several times
+    ///
+    /// This blocks for at least a few tens of milliseconds.
+    /// The accuracy is the latest any of the sleeps woke up.
+    pub fn calibrated() -> SpinSleeper {
+        let requested = Duration::from_millis(1);
+        let native_accuracy = (0..CALIBRATION_SAMPLES)
+            .map(|_| {
+                let start = Instant::now();
+                thread::sleep(requested);
+                start.elapsed().saturating_sub(requested)
+            })
+            .max()
+            .unwrap_or_default();
+        SpinSleeper { native_accuracy }
+    }
+    /// Gets how long before each deadline the [`SpinSleeper`] starts spinning
+    pub fn native_accuracy(&self) -> Duration {
+        self.native_accuracy
+    }
+    /// Blocks the current thread for the given number of floating-point seconds
+    pub fn sleep<D: ToDuration>(&self, time: D) {
+        self.sleep_until(Instant::now() + time.to_duration());
+    }
+    /// Blocks the current thread until the given [`Instant`]
+    pub fn sleep_until(&self, deadline: Instant) {
+        let coarse = deadline
+            .saturating_duration_since(Instant::now())
+            .saturating_sub(self.native_accuracy);
+        if coarse > Duration::from_secs(0) {
+            thread::sleep(coarse);
+        }
+        while Instant::now() < deadline {
+            hint::spin_loop();
+        }
+    }
+}
+
+impl Default for SpinSleeper {
+    /// Creates a new [`SpinSleeper`] with a typical native sleep accuracy for the platform
+    fn default() -> Self {
+        let native_accuracy = if cfg!(windows) {
+            Duration::from_millis(16)
+        } else {
+            Duration::from_millis(1)
+        };
+        SpinSleeper { native_accuracy }
+    }
+}
+
+impl Timer {
+    /// Blocks the current thread until the [`Timer`] is ready
+    ///
+    /// This uses a default [`SpinSleeper`], so it wakes up very close to when the [`Timer`] ends.
+    pub fn wait(&self) {
+        self.wait_with(&SpinSleeper::default());
+    }
+    /// Blocks the current thread until the [`Timer`] is ready, using the given [`SpinSleeper`]
+    pub fn wait_with(&self, sleeper: &SpinSleeper) {
+        sleeper.sleep_until(self.ends_at());
+    }
+}
+
+impl Ticker {
+    /// Blocks the current thread until the next tick using the given [`SpinSleeper`],
+    /// then returns the number of ticks that have happened since the last call to this or [`Ticker::ticks`]
+    ///
+    /// This is useful for limiting a frame rate precisely.
+    /// The result is always at least 1.
+    pub fn wait_with(&mut self, sleeper: &SpinSleeper) -> u64 {
+        loop {
+            sleeper.sleep(self.time_until_next());
+            let ticks = self.ticks();
+            if ticks > 0 {
+                return ticks;
+            }
+        }
+    }
+}
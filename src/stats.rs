@@ -0,0 +1,345 @@
+use std::{
+    collections::{hash_map::RandomState, BTreeMap},
+    convert::TryFrom,
+    hash::{BuildHasher, Hasher},
+    time::Duration,
+};
+
+/// An online accumulator of statistics about recorded [`Duration`]s
+///
+/// Statistics are updated with each recorded [`Duration`] using Welford's algorithm,
+/// so memory use is constant no matter how many [`Duration`]s are recorded.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct TimingStats {
+    count: u64,
+    min: Duration,
+    max: Duration,
+    total: Duration,
+    mean: f64,
+    m2: f64,
+}
+
+impl TimingStats {
+    /// Creates a new empty [`TimingStats`]
+    pub fn new() -> TimingStats {
+        TimingStats::default()
+    }
+    /// Records a [`Duration`]
+    pub fn record(&mut self, duration: Duration) {
+        let secs = duration.as_secs_f64();
+        if self.count == 0 {
+            self.min = duration;
+            self.max = duration;
+        } else {
+            self.min = self.min.min(duration);
+            self.max = self.max.max(duration);
+        }
+        self.count += 1;
+        self.total += duration;
+        let delta = secs - self.mean;
+        self.mean += delta / self.count as f64;
+        self.m2 += delta * (secs - self.mean);
+    }
+    /// Combines the statistics of another [`TimingStats`] into this one
+    pub fn merge(&mut self, other: &TimingStats) {
+        if other.count == 0 {
+            return;
+        }
+        if self.count == 0 {
+            *self = *other;
+            return;
+        }
+        let count = self.count + other.count;
+        let delta = other.mean - self.mean;
+        self.mean += delta * other.count as f64 / count as f64;
+        self.m2 += other.m2 + delta * delta * self.count as f64 * other.count as f64 / count as f64;
+        self.count = count;
+        self.total += other.total;
+        self.min = self.min.min(other.min);
+        self.max = self.max.max(other.max);
+    }
+    /// Gets the number of recorded [`Duration`]s
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+    /// Gets the sum of all recorded [`Duration`]s
+    pub fn total(&self) -> Duration {
+        self.total
+    }
+    /// Gets the shortest recorded [`Duration`], or `None` if nothing has been recorded
+    pub fn min(&self) -> Option<Duration> {
+        self.recorded(self.min)
+    }
+    /// Gets the longest recorded [`Duration`], or `None` if nothing has been recorded
+    pub fn max(&self) -> Option<Duration> {
+        self.recorded(self.max)
+    }
+    /// Gets the mean of the recorded [`Duration`]s, or `None` if nothing has been recorded
+    pub fn mean(&self) -> Option<Duration> {
+        self.recorded(Duration::from_secs_f64(self.mean))
+    }
+    /// Gets the sample variance of the recorded [`Duration`]s in seconds squared,
+    /// or `None` if fewer than two [`Duration`]s have been recorded
+    pub fn variance(&self) -> Option<f64> {
+        if self.count < 2 {
+            None
+        } else {
+            Some(self.m2 / (self.count - 1) as f64)
+        }
+    }
+    /// Gets the sample standard deviation of the recorded [`Duration`]s,
+    /// or `None` if fewer than two [`Duration`]s have been recorded
+    pub fn std_dev(&self) -> Option<Duration> {
+        self.variance()
+            .map(|variance| Duration::from_secs_f64(variance.sqrt()))
+    }
+    /// Clears all recorded statistics
+    pub fn reset(&mut self) {
+        *self = TimingStats::default();
+    }
+    fn recorded(&self, duration: Duration) -> Option<Duration> {
+        if self.count == 0 {
+            None
+        } else {
+            Some(duration)
+        }
+    }
+}
+
+impl Extend<Duration> for TimingStats {
+    fn extend<I: IntoIterator<Item = Duration>>(&mut self, iter: I) {
+        for duration in iter {
+            self.record(duration);
+        }
+    }
+}
+
+impl std::iter::FromIterator<Duration> for TimingStats {
+    fn from_iter<I: IntoIterator<Item = Duration>>(iter: I) -> Self {
+        let mut stats = TimingStats::new();
+        stats.extend(iter);
+        stats
+    }
+}
+
+/// A small pseudorandom number generator for sampling
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub(crate) struct XorShift(u64);
+
+impl XorShift {
+    pub(crate) fn new() -> XorShift {
+        let seed = RandomState::new().build_hasher().finish();
+        XorShift(seed | 1)
+    }
+    /// Gets a number in `0..bound`
+    pub(crate) fn below(&mut self, bound: u64) -> u64 {
+        self.0 ^= self.0 >> 12;
+        self.0 ^= self.0 << 25;
+        self.0 ^= self.0 >> 27;
+        let x = self.0.wrapping_mul(0x2545_f491_4f6c_dd1d);
+        ((u128::from(x) * u128::from(bound)) >> 64) as u64
+    }
+}
+
+/// A fixed-size random sample of recorded [`Duration`]s
+///
+/// Every recorded [`Duration`] has an equal chance of being in the sample, no matter how
+/// many are recorded, so percentiles of the sample approximate percentiles of everything
+/// that was recorded while using a fixed amount of memory.
+#[derive(Debug, Clone)]
+pub struct Reservoir {
+    samples: Vec<Duration>,
+    capacity: usize,
+    count: u64,
+    rng: XorShift,
+}
+
+impl Reservoir {
+    /// Creates a new empty [`Reservoir`] that keeps at most the given number of samples
+    ///
+    /// # Panics
+    ///
+    /// Panics if the capacity is 0
+    pub fn new(capacity: usize) -> Reservoir {
+        assert!(capacity > 0, "Reservoir capacity must be at least 1");
+        Reservoir {
+            samples: Vec::with_capacity(capacity),
+            capacity,
+            count: 0,
+            rng: XorShift::new(),
+        }
+    }
+    /// Records a [`Duration`]
+    pub fn record(&mut self, duration: Duration) {
+        self.count += 1;
+        if self.samples.len() < self.capacity {
+            self.samples.push(duration);
+        } else {
+            let i = self.rng.below(self.count) as usize;
+            if i < self.capacity {
+                self.samples[i] = duration;
+            }
+        }
+    }
+    /// Gets the maximum number of samples kept
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+    /// Gets the total number of [`Duration`]s recorded
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+    /// Gets the sampled [`Duration`]s in no particular order
+    pub fn samples(&self) -> &[Duration] {
+        &self.samples
+    }
+    /// Gets the approximate given percentile of the recorded [`Duration`]s
+    ///
+    /// `percentile` is clamped to the range `0.0..=100.0`.
+    /// Returns `None` if nothing has been recorded.
+    pub fn percentile(&self, percentile: f64) -> Option<Duration> {
+        if self.samples.is_empty() {
+            return None;
+        }
+        let mut sorted = self.samples.clone();
+        sorted.sort_unstable();
+        let rank = (percentile.clamp(0.0, 100.0) / 100.0 * (sorted.len() - 1) as f64).round();
+        Some(sorted[rank as usize])
+    }
+    /// Clears all samples
+    pub fn reset(&mut self) {
+        self.samples.clear();
+        self.count = 0;
+    }
+}
+
+impl Extend<Duration> for Reservoir {
+    fn extend<I: IntoIterator<Item = Duration>>(&mut self, iter: I) {
+        for duration in iter {
+            self.record(duration);
+        }
+    }
+}
+
+/// A histogram of recorded [`Duration`]s with log-linear buckets
+///
+/// Buckets are laid out like those of an HDR histogram: [`Duration`]s are grouped so that
+/// every bucket's range is within the configured number of significant decimal figures
+/// of its values. This keeps the relative error bounded for both very short and very
+/// long [`Duration`]s while using little memory. Only non-empty buckets are stored.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Histogram {
+    significant_figures: u8,
+    sub_bucket_bits: u32,
+    buckets: BTreeMap<u64, u64>,
+    count: u64,
+    total: Duration,
+}
+
+impl Histogram {
+    /// Creates a new empty [`Histogram`] whose buckets are precise
+    /// to the given number of significant decimal figures
+    ///
+    /// # Panics
+    ///
+    /// Panics if `significant_figures` is not in the range `1..=5`
+    pub fn new(significant_figures: u8) -> Histogram {
+        assert!(
+            (1..=5).contains(&significant_figures),
+            "Histogram significant figures must be between 1 and 5"
+        );
+        let largest_exact = 2 * 10u64.pow(u32::from(significant_figures));
+        Histogram {
+            significant_figures,
+            sub_bucket_bits: 64 - (largest_exact - 1).leading_zeros(),
+            buckets: BTreeMap::new(),
+            count: 0,
+            total: Duration::from_secs(0),
+        }
+    }
+    /// Gets the number of significant decimal figures the buckets are precise to
+    pub fn significant_figures(&self) -> u8 {
+        self.significant_figures
+    }
+    /// Records a [`Duration`]
+    pub fn record(&mut self, duration: Duration) {
+        self.record_n(duration, 1);
+    }
+    /// Records a [`Duration`] the given number of times
+    pub fn record_n(&mut self, duration: Duration, count: u64) {
+        if count == 0 {
+            return;
+        }
+        let nanos = u64::try_from(duration.as_nanos()).unwrap_or(u64::MAX);
+        *self.buckets.entry(self.upper_bound(nanos)).or_insert(0) += count;
+        self.count += count;
+        self.total = self
+            .total
+            .saturating_add(duration.saturating_mul(u32::try_from(count).unwrap_or(u32::MAX)));
+    }
+    /// Combines the counts of another [`Histogram`] into this one
+    ///
+    /// If the other histogram is more precise, its counts are re-bucketed at this histogram's precision.
+    pub fn merge(&mut self, other: &Histogram) {
+        for (&upper, &count) in &other.buckets {
+            let nanos = self.upper_bound(upper);
+            *self.buckets.entry(nanos).or_insert(0) += count;
+        }
+        self.count += other.count;
+        self.total = self.total.saturating_add(other.total);
+    }
+    /// Gets the number of recorded [`Duration`]s
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+    /// Gets the sum of all recorded [`Duration`]s
+    pub fn total(&self) -> Duration {
+        self.total
+    }
+    /// Gets the inclusive upper bound and count of each non-empty bucket, from shortest to longest
+    pub fn buckets(&self) -> Vec<(Duration, u64)> {
+        self.buckets
+            .iter()
+            .map(|(&upper, &count)| (Duration::from_nanos(upper), count))
+            .collect()
+    }
+    /// Gets the given percentile of the recorded [`Duration`]s, rounded up to its bucket's upper bound
+    ///
+    /// `percentile` is clamped to the range `0.0..=100.0`.
+    /// Returns `None` if nothing has been recorded.
+    pub fn percentile(&self, percentile: f64) -> Option<Duration> {
+        let target =
+            ((percentile.clamp(0.0, 100.0) / 100.0 * self.count as f64).ceil() as u64).max(1);
+        let mut seen = 0;
+        for (&upper, &count) in &self.buckets {
+            seen += count;
+            if seen >= target {
+                return Some(Duration::from_nanos(upper));
+            }
+        }
+        None
+    }
+    /// Clears all counts
+    pub fn reset(&mut self) {
+        self.buckets.clear();
+        self.count = 0;
+        self.total = Duration::from_secs(0);
+    }
+    /// Gets the inclusive upper bound in nanoseconds of the bucket that holds the given number of nanoseconds
+    fn upper_bound(&self, nanos: u64) -> u64 {
+        let magnitude = 64 - nanos.leading_zeros();
+        if magnitude <= self.sub_bucket_bits {
+            return nanos;
+        }
+        let shift = magnitude - self.sub_bucket_bits;
+        (((nanos >> shift) + 1) << shift).wrapping_sub(1)
+    }
+}
+
+impl Extend<Duration> for Histogram {
+    fn extend<I: IntoIterator<Item = Duration>>(&mut self, iter: I) {
+        for duration in iter {
+            self.record(duration);
+        }
+    }
+}
@@ -0,0 +1,391 @@
+use std::{
+    convert::TryFrom,
+    thread,
+    time::{Duration, Instant},
+};
+
+use crate::{stats::XorShift, ToDuration};
+
+/**
+A frequency in cycles per second
+
+Game and audio code often thinks in rates rather than periods.
+A [`Hertz`] can be used anywhere a [`ToDuration`] is expected, in which case it is converted to its period.
+
+# Example
+```
+use std::time::Duration;
+use eggtimer::{Hertz, ToHertz};
+
+assert_eq!(Hertz(4.0).period(), Duration::from_millis(250));
+assert_eq!(50.hz().period(), Duration::from_millis(20));
+```
+*/
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Default)]
+pub struct Hertz(pub f64);
+
+impl Hertz {
+    /// Gets the time one cycle takes
+    ///
+    /// # Panics
+    ///
+    /// Panics if the frequency is not positive and finite
+    pub fn period(self) -> Duration {
+        assert!(
+            self.0 > 0.0 && self.0.is_finite(),
+            "Hertz must be positive and finite to have a period"
+        );
+        Duration::from_secs_f64(1.0 / self.0)
+    }
+    /// Creates a [`Hertz`] whose period is the given number of floating-point seconds
+    pub fn from_period<D: ToDuration>(period: D) -> Hertz {
+        Hertz(1.0 / period.to_duration().as_secs_f64())
+    }
+}
+
+impl ToDuration for Hertz {
+    fn to_duration(&self) -> Duration {
+        self.period()
+    }
+}
+
+/// A trait for numbers that can be turned into a [`Hertz`]
+pub trait ToHertz {
+    /// Creates a [`Hertz`] with this many cycles per second
+    fn hz(self) -> Hertz;
+}
+
+macro_rules! to_hertz {
+    ($($ty:ty),*) => {
+        $(
+            impl ToHertz for $ty {
+                fn hz(self) -> Hertz {
+                    Hertz(f64::from(self))
+                }
+            }
+        )*
+    };
+}
+
+to_hertz!(u8, u16, u32, i32, f32, f64);
+
+impl ToHertz for i64 {
+    fn hz(self) -> Hertz {
+        Hertz(self as f64)
+    }
+}
+
+/// Random variation applied to the ticks of a [`Ticker`]
+///
+/// Jitter keeps many clients with the same period from all ticking at the same moment.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub enum Jitter {
+    /// Tick exactly at the end of each period
+    #[default]
+    None,
+    /// Tick at the end of each period plus or minus up to the given [`Duration`]
+    ///
+    /// The jitter is capped at half the period so that ticks stay in order.
+    Uniform(Duration),
+    /// Tick at a uniformly random point within each period
+    Full,
+}
+
+/**
+A ticker that ticks at a fixed rate
+
+Ticks are scheduled relative to when the [`Ticker`] was created, so they do not drift
+no matter how late they are observed. Individual ticks can be randomly offset with [`Jitter`]
+without affecting the long-run rate.
+
+# Example
+```
+use eggtimer::{Ticker, ToHertz};
+
+let mut ticker = Ticker::at(100.hz());
+for _ in 0..3 {
+    let ticks = ticker.wait();
+    assert!(ticks >= 1);
+}
+```
+*/
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Ticker {
+    period: Duration,
+    period_end: Instant,
+    next: Instant,
+    jitter: Jitter,
+    rng: XorShift,
+}
+
+impl Ticker {
+    /// Creates a new [`Ticker`] that ticks every given number of floating-point seconds
+    ///
+    /// The first tick is one period from now.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the period is zero
+    pub fn new<D: ToDuration>(period: D) -> Ticker {
+        let period = period.to_duration();
+        assert!(
+            period > Duration::from_secs(0),
+            "Ticker period must not be zero"
+        );
+        let period_end = Instant::now() + period;
+        Ticker {
+            period,
+            period_end,
+            next: period_end,
+            jitter: Jitter::None,
+            rng: XorShift::new(),
+        }
+    }
+    /// Creates a new [`Ticker`] that ticks at the given frequency
+    pub fn at(frequency: Hertz) -> Ticker {
+        Ticker::new(frequency.period())
+    }
+    /// Sets the [`Jitter`] applied to each tick, starting with the next one
+    ///
+    /// # Example
+    /// ```
+    /// use eggtimer::{Jitter, Ticker};
+    ///
+    /// let ticker = Ticker::new(30.0).with_jitter(Jitter::Full);
+    /// ```
+    pub fn with_jitter(mut self, jitter: Jitter) -> Ticker {
+        self.jitter = jitter;
+        self.schedule();
+        self
+    }
+    /// Gets the time between ticks
+    pub fn period(&self) -> Duration {
+        self.period
+    }
+    /// Gets the [`Jitter`] applied to each tick
+    pub fn jitter(&self) -> Jitter {
+        self.jitter
+    }
+    /// Gets the time until the next tick
+    pub fn time_until_next(&self) -> Duration {
+        self.next.saturating_duration_since(Instant::now())
+    }
+    /// Gets the [`Instant`] of the next tick
+    #[cfg(feature = "async")]
+    pub(crate) fn next_tick(&self) -> Instant {
+        self.next
+    }
+    /// Gets the number of ticks that have happened since the last call to this or [`Ticker::wait`]
+    pub fn ticks(&mut self) -> u64 {
+        let now = Instant::now();
+        let mut ticks = 0;
+        while now >= self.next {
+            let period = self.period.as_nanos();
+            let skipped = now.saturating_duration_since(self.period_end).as_nanos() / period;
+            self.period_end +=
+                Duration::from_nanos(u64::try_from((skipped + 1) * period).unwrap_or(u64::MAX));
+            ticks += u64::try_from(skipped).unwrap_or(u64::MAX).saturating_add(1);
+            self.schedule();
+        }
+        ticks
+    }
+    /// Blocks the current thread until the next tick, then returns the number of
+    /// ticks that have happened since the last call to this or [`Ticker::ticks`]
+    ///
+    /// The result is always at least 1.
+    pub fn wait(&mut self) -> u64 {
+        loop {
+            thread::sleep(self.time_until_next());
+            let ticks = self.ticks();
+            if ticks > 0 {
+                return ticks;
+            }
+        }
+    }
+    /// Schedules the tick for the current period
+    fn schedule(&mut self) {
+        let period = u64::try_from(self.period.as_nanos()).unwrap_or(u64::MAX);
+        self.next = match self.jitter {
+            Jitter::None => self.period_end,
+            Jitter::Uniform(max) => {
+                let max = u64::try_from(max.as_nanos())
+                    .unwrap_or(u64::MAX)
+                    .min(period / 2);
+                let offset = self.rng.below(max.saturating_mul(2).saturating_add(1));
+                self.period_end - Duration::from_nanos(max) + Duration::from_nanos(offset)
+            }
+            Jitter::Full => {
+                self.period_end - self.period + Duration::from_nanos(self.rng.below(period))
+            }
+        };
+    }
+}
+
+/**
+A metronome that counts beats at a tempo given in beats per minute
+
+Changing the tempo keeps the current position within the beat, so there are no phase jumps.
+
+# Example
+```
+use eggtimer::Metronome;
+
+let mut metronome = Metronome::new(120.0);
+assert_eq!(metronome.beat_index(), 0);
+assert!(metronome.fraction_into_beat() < 1.0);
+metronome.set_bpm(90.0);
+```
+*/
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Metronome {
+    bpm: f64,
+    anchor: Instant,
+    anchor_beats: f64,
+    last_beat: u64,
+}
+
+impl Metronome {
+    /// Creates a new [`Metronome`] with the given tempo in beats per minute
+    ///
+    /// Beat 0 starts now.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bpm` is not positive and finite
+    pub fn new(bpm: f64) -> Metronome {
+        assert_tempo(bpm);
+        Metronome {
+            bpm,
+            anchor: Instant::now(),
+            anchor_beats: 0.0,
+            last_beat: 0,
+        }
+    }
+    /// Gets the tempo in beats per minute
+    pub fn bpm(&self) -> f64 {
+        self.bpm
+    }
+    /// Changes the tempo without changing the current position within the beat
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bpm` is not positive and finite
+    pub fn set_bpm(&mut self, bpm: f64) {
+        assert_tempo(bpm);
+        let now = Instant::now();
+        self.anchor_beats = self.beats_at(now);
+        self.anchor = now;
+        self.bpm = bpm;
+    }
+    /// Gets the length of one beat at the current tempo
+    pub fn beat_duration(&self) -> Duration {
+        Duration::from_secs_f64(60.0 / self.bpm)
+    }
+    /// Gets the number of beats since the [`Metronome`] started, including the fraction of the current beat
+    pub fn beats(&self) -> f64 {
+        self.beats_at(Instant::now())
+    }
+    /// Gets the index of the current beat
+    pub fn beat_index(&self) -> u64 {
+        self.beats() as u64
+    }
+    /// Gets how far into the current beat the [`Metronome`] is, between 0 and 1
+    pub fn fraction_into_beat(&self) -> f64 {
+        self.beats().fract()
+    }
+    /// Gets the number of beats that have started since the last call to this function
+    ///
+    /// The first call counts from when the [`Metronome`] was created.
+    pub fn beats_elapsed_since_last_call(&mut self) -> u64 {
+        let index = self.beat_index();
+        let elapsed = index - self.last_beat;
+        self.last_beat = index;
+        elapsed
+    }
+    fn beats_at(&self, instant: Instant) -> f64 {
+        self.anchor_beats
+            + instant.saturating_duration_since(self.anchor).as_secs_f64() * self.bpm / 60.0
+    }
+}
+
+fn assert_tempo(bpm: f64) {
+    assert!(
+        bpm > 0.0 && bpm.is_finite(),
+        "Metronome tempo must be positive and finite"
+    );
+}
+
+#[cfg(feature = "async")]
+impl Ticker {
+    /**
+    Turns the [`Ticker`] into a stream of its ticks
+
+    Pending polls are woken by a shared background thread, so this works with any async runtime.
+
+    # Example
+    ```
+    use std::{future::poll_fn, pin::Pin};
+    use eggtimer::Ticker;
+    use futures_core::Stream;
+
+    # struct Noop;
+    # impl std::task::Wake for Noop {
+    #     fn wake(self: std::sync::Arc<Self>) {}
+    # }
+    # let waker = std::task::Waker::from(std::sync::Arc::new(Noop));
+    # let mut cx = std::task::Context::from_waker(&waker);
+    let mut ticks = Ticker::new(0.01).into_stream();
+    assert!(Pin::new(&mut ticks).poll_next(&mut cx).is_pending());
+    ```
+    */
+    pub fn into_stream(self) -> Ticks {
+        Ticks {
+            ticker: self,
+            registration: crate::waker::Registration::default(),
+        }
+    }
+}
+
+/// A stream of the ticks of a [`Ticker`]
+///
+/// This is created by [`Ticker::into_stream`]. It yields the number of ticks
+/// that have happened since the last one was yielded, and never ends.
+/// Dropping it stops the shared background thread from waiting on its next tick.
+#[cfg(feature = "async")]
+#[derive(Debug)]
+pub struct Ticks {
+    ticker: Ticker,
+    registration: crate::waker::Registration,
+}
+
+#[cfg(feature = "async")]
+impl Ticks {
+    /// Gets the underlying [`Ticker`]
+    pub fn ticker(&self) -> &Ticker {
+        &self.ticker
+    }
+    /// Turns the stream back into its [`Ticker`]
+    pub fn into_ticker(self) -> Ticker {
+        self.ticker
+    }
+}
+
+#[cfg(feature = "async")]
+impl futures_core::Stream for Ticks {
+    type Item = u64;
+    fn poll_next(
+        mut self: std::pin::Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Option<u64>> {
+        let this = &mut *self;
+        let ticks = this.ticker.ticks();
+        if ticks > 0 {
+            this.registration.clear();
+            std::task::Poll::Ready(Some(ticks))
+        } else {
+            this.registration
+                .register(this.ticker.next_tick(), cx.waker());
+            std::task::Poll::Pending
+        }
+    }
+}
@@ -0,0 +1,137 @@
+use std::{
+    convert::TryFrom,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
+
+/**
+Call statistics recorded by a [`TimedFn`]
+
+These are updated atomically, so they can be read from any thread while the function is being called.
+*/
+#[derive(Debug, Default)]
+pub struct CallStats {
+    count: AtomicU64,
+    total: AtomicU64,
+    max: AtomicU64,
+}
+
+impl CallStats {
+    /// Gets the number of completed calls
+    pub fn count(&self) -> u64 {
+        self.count.load(Ordering::Relaxed)
+    }
+    /// Gets the total time spent in all completed calls
+    pub fn total(&self) -> Duration {
+        Duration::from_nanos(self.total.load(Ordering::Relaxed))
+    }
+    /// Gets the longest time a single call took
+    pub fn max(&self) -> Duration {
+        Duration::from_nanos(self.max.load(Ordering::Relaxed))
+    }
+    /// Gets the mean time a call took
+    ///
+    /// Returns `None` if there have been no calls.
+    pub fn mean(&self) -> Option<Duration> {
+        self.total
+            .load(Ordering::Relaxed)
+            .checked_div(self.count())
+            .map(Duration::from_nanos)
+    }
+    fn record(&self, dur: Duration) {
+        let nanos = u64::try_from(dur.as_nanos()).unwrap_or(u64::MAX);
+        self.count.fetch_add(1, Ordering::Relaxed);
+        self.total.fetch_add(nanos, Ordering::Relaxed);
+        self.max.fetch_max(nanos, Ordering::Relaxed);
+    }
+}
+
+/**
+A wrapper around a function that records how many times it was called and how long the calls took
+
+Stable Rust does not allow implementing the `Fn` traits for custom types, so the wrapped function
+is invoked with [`TimedFn::call`], which takes its arguments as a tuple. To hand a [`TimedFn`] to
+code that expects a closure, wrap the call in one. The [`CallStats`] can be kept to inspect later.
+
+# Example
+```
+use eggtimer::TimedFn;
+
+let timed = TimedFn::new(|a: i32, b: i32| a + b);
+let stats = timed.stats();
+let callback = move |a, b| timed.call((a, b));
+
+assert_eq!(callback(1, 2), 3);
+assert_eq!(callback(3, 4), 7);
+assert_eq!(stats.count(), 2);
+```
+*/
+#[derive(Debug)]
+pub struct TimedFn<F> {
+    f: F,
+    stats: Arc<CallStats>,
+}
+
+impl<F> TimedFn<F> {
+    /// Wraps a function in a [`TimedFn`]
+    pub fn new(f: F) -> TimedFn<F> {
+        TimedFn {
+            f,
+            stats: Arc::new(CallStats::default()),
+        }
+    }
+    /// Calls the wrapped function with a tuple of arguments, recording how long the call takes
+    pub fn call<Args>(&self, args: Args) -> F::Output
+    where
+        F: TimedCall<Args>,
+    {
+        let start = Instant::now();
+        let output = self.f.call_with(args);
+        self.stats.record(start.elapsed());
+        output
+    }
+    /// Gets a shared handle to the recorded [`CallStats`]
+    pub fn stats(&self) -> Arc<CallStats> {
+        Arc::clone(&self.stats)
+    }
+    /// Unwraps the wrapped function
+    pub fn into_inner(self) -> F {
+        self.f
+    }
+}
+
+/// A function that can be called by a [`TimedFn`] with a tuple of arguments
+///
+/// This is implemented for all [`Fn`]s with up to 6 arguments.
+pub trait TimedCall<Args> {
+    /// The function's return type
+    type Output;
+    /// Calls the function with a tuple of arguments
+    fn call_with(&self, args: Args) -> Self::Output;
+}
+
+macro_rules! timed_call {
+    ($($arg:ident),*) => {
+        impl<F, R, $($arg),*> TimedCall<($($arg,)*)> for F
+        where
+            F: Fn($($arg),*) -> R,
+        {
+            type Output = R;
+            #[allow(non_snake_case)]
+            fn call_with(&self, ($($arg,)*): ($($arg,)*)) -> R {
+                self($($arg),*)
+            }
+        }
+    };
+}
+
+timed_call!();
+timed_call!(A);
+timed_call!(A, B);
+timed_call!(A, B, C);
+timed_call!(A, B, C, D);
+timed_call!(A, B, C, D, E);
+timed_call!(A, B, C, D, E, G);
@@ -0,0 +1,116 @@
+use std::{
+    convert::TryFrom,
+    ops::{Add, Sub},
+    sync::OnceLock,
+    time::{Duration, Instant},
+};
+
+use crate::{Elapsed, ToDuration};
+
+static EPOCH: OnceLock<Instant> = OnceLock::new();
+
+/// Gets the process-wide epoch, initializing it if this is the first use
+fn epoch() -> Instant {
+    *EPOCH.get_or_init(Instant::now)
+}
+
+/**
+A compact monotonic timestamp
+
+A [`Timestamp`] stores the nanoseconds since a process-wide epoch in a [`u64`],
+so it is half the size of an [`Instant`] on most platforms and can be serialized.
+The epoch is fixed the first time any [`Timestamp`] is created, so timestamps are only
+meaningful within the process that created them. Times before the epoch saturate to it.
+
+# Example
+```
+use eggtimer::Timestamp;
+
+let events: Vec<Timestamp> = (0..3).map(|_| Timestamp::now()).collect();
+assert!(events[0] <= events[2]);
+let instant = events[1].to_instant();
+assert_eq!(Timestamp::from(instant), events[1]);
+```
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Timestamp(u64);
+
+impl Timestamp {
+    /// Gets the current [`Timestamp`]
+    pub fn now() -> Timestamp {
+        Timestamp::from(Instant::now())
+    }
+    /// Creates a [`Timestamp`] from a number of nanoseconds since the epoch
+    pub fn from_nanos(nanos: u64) -> Timestamp {
+        Timestamp(nanos)
+    }
+    /// Gets the number of nanoseconds since the epoch
+    pub fn as_nanos(self) -> u64 {
+        self.0
+    }
+    /// Converts the [`Timestamp`] to an [`Instant`]
+    pub fn to_instant(self) -> Instant {
+        epoch() + Duration::from_nanos(self.0)
+    }
+    /// Gets the time since this [`Timestamp`]
+    pub fn elapsed(self) -> Duration {
+        Timestamp::now().duration_since(self)
+    }
+    /// Gets the time from an earlier [`Timestamp`] to this one, or zero if it is actually later
+    pub fn duration_since(self, earlier: Timestamp) -> Duration {
+        Duration::from_nanos(self.0.saturating_sub(earlier.0))
+    }
+}
+
+impl From<Instant> for Timestamp {
+    fn from(instant: Instant) -> Self {
+        let nanos = instant.saturating_duration_since(epoch()).as_nanos();
+        Timestamp(u64::try_from(nanos).unwrap_or(u64::MAX))
+    }
+}
+
+impl From<Timestamp> for Instant {
+    fn from(timestamp: Timestamp) -> Self {
+        timestamp.to_instant()
+    }
+}
+
+/// Gets the [`Timestamp`] at which the [`Elapsed`] was started
+impl From<Elapsed> for Timestamp {
+    fn from(elapsed: Elapsed) -> Self {
+        Timestamp::from(elapsed.started_at())
+    }
+}
+
+/// Creates an [`Elapsed`] that was started at the [`Timestamp`]
+impl From<Timestamp> for Elapsed {
+    fn from(timestamp: Timestamp) -> Self {
+        Elapsed {
+            start: timestamp.to_instant(),
+        }
+    }
+}
+
+/// The time since the epoch
+impl ToDuration for Timestamp {
+    fn to_duration(&self) -> Duration {
+        Duration::from_nanos(self.0)
+    }
+}
+
+impl Add<Duration> for Timestamp {
+    type Output = Timestamp;
+    fn add(self, time: Duration) -> Timestamp {
+        let nanos = u64::try_from(time.as_nanos()).unwrap_or(u64::MAX);
+        Timestamp(self.0.saturating_add(nanos))
+    }
+}
+
+impl Sub<Duration> for Timestamp {
+    type Output = Timestamp;
+    fn sub(self, time: Duration) -> Timestamp {
+        let nanos = u64::try_from(time.as_nanos()).unwrap_or(u64::MAX);
+        Timestamp(self.0.saturating_sub(nanos))
+    }
+}
@@ -0,0 +1,306 @@
+use std::{convert::TryFrom, fmt, time::Duration};
+
+use crate::{FromDuration, ToDuration};
+
+/// How to round when converting a [`Duration`] to a whole number of units
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub enum Rounding {
+    /// Round down, discarding any partial unit
+    #[default]
+    Floor,
+    /// Round up, counting any partial unit as a whole one
+    Ceil,
+    /// Round to the nearest whole unit, rounding halfway cases up
+    Nearest,
+}
+
+/**
+A trait for whole-unit types that can be created from a [`Duration`] with explicit rounding
+
+[`FromDuration`] always rounds down, which is not always what you want.
+For example, a countdown display should usually round up so that it does not
+show 0 while there is still time left.
+
+# Example
+```
+use std::time::Duration;
+use eggtimer::{FromDurationRounded, Rounding};
+
+let left = Duration::from_millis(900);
+assert_eq!(u64::from_duration_rounded(left, Rounding::Floor), 0);
+assert_eq!(u64::from_duration_rounded(left, Rounding::Ceil), 1);
+assert_eq!(u64::from_duration_rounded(left, Rounding::Nearest), 1);
+```
+*/
+pub trait FromDurationRounded: FromDuration {
+    /// Create the value from a [`Duration`], rounding in the given way
+    fn from_duration_rounded(duration: Duration, rounding: Rounding) -> Self;
+}
+
+impl FromDurationRounded for u64 {
+    fn from_duration_rounded(duration: Duration, rounding: Rounding) -> Self {
+        u64::try_from(rounded_count(duration, 1_000_000_000, rounding)).unwrap_or(u64::MAX)
+    }
+}
+
+impl FromDurationRounded for u128 {
+    fn from_duration_rounded(duration: Duration, rounding: Rounding) -> Self {
+        rounded_count(duration, 1_000_000_000, rounding)
+    }
+}
+
+impl FromDurationRounded for usize {
+    fn from_duration_rounded(duration: Duration, rounding: Rounding) -> Self {
+        usize::try_from(rounded_count(duration, 1_000_000_000, rounding)).unwrap_or(usize::MAX)
+    }
+}
+
+/// Counts the whole units of the given number of nanoseconds in a [`Duration`]
+fn rounded_count(duration: Duration, unit: u128, rounding: Rounding) -> u128 {
+    let nanos = duration.as_nanos();
+    let count = nanos / unit;
+    let rem = nanos % unit;
+    match rounding {
+        Rounding::Floor => count,
+        Rounding::Ceil if rem > 0 => count + 1,
+        Rounding::Ceil => count,
+        Rounding::Nearest if rem * 2 >= unit => count + 1,
+        Rounding::Nearest => count,
+    }
+}
+
+macro_rules! unit {
+    ($(#[$attr:meta])* $name:ident, $per_sec:expr, $as:ident, $suffix:expr) => {
+        $(#[$attr])*
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+        pub struct $name(pub u128);
+
+        impl FromDuration for $name {
+            fn from_duration(duration: Duration) -> Self {
+                $name(duration.$as())
+            }
+        }
+
+        impl ToDuration for $name {
+            fn to_duration(&self) -> Duration {
+                let secs = u64::try_from(self.0 / $per_sec).unwrap_or(u64::MAX);
+                let nanos = (self.0 % $per_sec) as u32 * (1_000_000_000 / $per_sec as u32);
+                Duration::new(secs, nanos)
+            }
+        }
+
+        impl FromDurationRounded for $name {
+            fn from_duration_rounded(duration: Duration, rounding: Rounding) -> Self {
+                $name(rounded_count(duration, 1_000_000_000 / $per_sec, rounding))
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                write!(f, "{}{}", self.0, $suffix)
+            }
+        }
+    };
+}
+
+unit!(
+    /**
+    A whole number of milliseconds
+
+    Unlike converting to an integer, which counts whole seconds,
+    converting a [`Duration`] to [`Millis`] keeps sub-second precision.
+
+    # Example
+    ```
+    use std::time::Duration;
+    use eggtimer::{FromDuration, Millis};
+
+    assert_eq!(Millis::from_duration(Duration::from_micros(1500)), Millis(1));
+    assert_eq!(u64::from_duration(Duration::from_micros(1500)), 0);
+    ```
+    */
+    Millis,
+    1_000,
+    as_millis,
+    "ms"
+);
+unit!(
+    /// A whole number of microseconds
+    Micros,
+    1_000_000,
+    as_micros,
+    "µs"
+);
+unit!(
+    /// A whole number of nanoseconds
+    Nanos,
+    1_000_000_000,
+    as_nanos,
+    "ns"
+);
+
+/**
+A number of whole seconds and a number of additional nanoseconds
+
+This is the same split as [`Duration::new`]. It is a tuple struct rather than a `(u64, u32)`
+[`ToDuration`] implementation because a second tuple implementation would stop integer literals
+in `(u64, u64)` minutes-and-seconds tuples from inferring without suffixes.
+Nanoseconds past a whole second carry over into the seconds, and the result saturates if it is too large.
+
+# Example
+```
+use std::time::Duration;
+use eggtimer::{SecsNanos, Timer};
+
+let timer = Timer::set(SecsNanos(1, 500_000_000));
+assert_eq!(timer.max_duration(), Duration::from_millis(1500));
+```
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct SecsNanos(pub u64, pub u32);
+
+impl FromDuration for SecsNanos {
+    fn from_duration(duration: Duration) -> Self {
+        SecsNanos(duration.as_secs(), duration.subsec_nanos())
+    }
+}
+
+impl ToDuration for SecsNanos {
+    fn to_duration(&self) -> Duration {
+        let secs = self.0.saturating_add(u64::from(self.1 / 1_000_000_000));
+        Duration::new(secs, self.1 % 1_000_000_000)
+    }
+}
+
+/// Snaps a [`Duration`] to a multiple of `granularity`, rounding in the given way
+///
+/// A zero granularity leaves the [`Duration`] unchanged. The result saturates if it is too large.
+fn snap_to(duration: Duration, granularity: Duration, rounding: Rounding) -> Duration {
+    let unit = granularity.as_nanos();
+    if unit == 0 {
+        return duration;
+    }
+    let nanos = rounded_count(duration, unit, rounding) * unit;
+    let secs = u64::try_from(nanos / 1_000_000_000).unwrap_or(u64::MAX);
+    Duration::new(secs, (nanos % 1_000_000_000) as u32)
+}
+
+/**
+Rounds a [`Duration`] to the nearest multiple of `granularity`
+
+Halfway cases round up. A zero granularity leaves the [`Duration`] unchanged.
+
+# Example
+```
+use std::time::Duration;
+use eggtimer::{ceil_to, floor_to, round_to};
+
+let d = Duration::from_millis(1_499);
+assert_eq!(round_to(d, 1u64), Duration::from_secs(1));
+assert_eq!(floor_to(d, Duration::from_millis(100)), Duration::from_millis(1_400));
+assert_eq!(ceil_to(d, 1u64), Duration::from_secs(2));
+```
+*/
+pub fn round_to<G: ToDuration>(duration: Duration, granularity: G) -> Duration {
+    snap_to(duration, granularity.to_duration(), Rounding::Nearest)
+}
+
+/// Rounds a [`Duration`] down to a multiple of `granularity`
+///
+/// A zero granularity leaves the [`Duration`] unchanged.
+pub fn floor_to<G: ToDuration>(duration: Duration, granularity: G) -> Duration {
+    snap_to(duration, granularity.to_duration(), Rounding::Floor)
+}
+
+/// Rounds a [`Duration`] up to a multiple of `granularity`
+///
+/// A zero granularity leaves the [`Duration`] unchanged.
+pub fn ceil_to<G: ToDuration>(duration: Duration, granularity: G) -> Duration {
+    snap_to(duration, granularity.to_duration(), Rounding::Ceil)
+}
+
+/// A unit for displaying a [`Duration`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub enum TimeUnit {
+    /// Seconds, shown as `s`
+    #[default]
+    Seconds,
+    /// Milliseconds, shown as `ms`
+    Millis,
+    /// Microseconds, shown as `µs`
+    Micros,
+    /// Nanoseconds, shown as `ns`
+    Nanos,
+}
+
+impl TimeUnit {
+    /// Gets the number of this unit in a [`Duration`], including the fraction
+    pub fn count(self, duration: Duration) -> f64 {
+        let secs = duration.as_secs_f64();
+        match self {
+            TimeUnit::Seconds => secs,
+            TimeUnit::Millis => secs * 1e3,
+            TimeUnit::Micros => secs * 1e6,
+            TimeUnit::Nanos => secs * 1e9,
+        }
+    }
+    /// Gets the suffix used when displaying this unit
+    pub fn suffix(self) -> &'static str {
+        match self {
+            TimeUnit::Seconds => "s",
+            TimeUnit::Millis => "ms",
+            TimeUnit::Micros => "µs",
+            TimeUnit::Nanos => "ns",
+        }
+    }
+}
+
+/**
+Creates a [`Duration`] from a number of whole seconds
+
+This and the other unit helpers are `const`, so durations can be put in `const`s and `static` tables
+and later used anywhere a [`ToDuration`] is expected.
+
+# Example
+```
+use std::time::Duration;
+use eggtimer::{millis, minutes, secs, Timer};
+
+static COOLDOWNS: [Duration; 3] = [millis(250), secs(5), minutes(2)];
+
+let timer = Timer::set(COOLDOWNS[1]);
+assert_eq!(timer.max_duration(), Duration::from_secs(5));
+```
+*/
+pub const fn secs(secs: u64) -> Duration {
+    Duration::from_secs(secs)
+}
+
+/// Creates a [`Duration`] from a number of whole milliseconds
+pub const fn millis(millis: u64) -> Duration {
+    Duration::from_millis(millis)
+}
+
+/// Creates a [`Duration`] from a number of whole microseconds
+pub const fn micros(micros: u64) -> Duration {
+    Duration::from_micros(micros)
+}
+
+/// Creates a [`Duration`] from a number of whole nanoseconds
+pub const fn nanos(nanos: u64) -> Duration {
+    Duration::from_nanos(nanos)
+}
+
+/// Creates a [`Duration`] from a number of whole minutes
+///
+/// The result saturates if it is too large.
+pub const fn minutes(minutes: u64) -> Duration {
+    Duration::from_secs(minutes.saturating_mul(60))
+}
+
+/// Creates a [`Duration`] from a number of whole hours
+///
+/// The result saturates if it is too large.
+pub const fn hours(hours: u64) -> Duration {
+    Duration::from_secs(hours.saturating_mul(60 * 60))
+}
@@ -0,0 +1,335 @@
+use std::{
+    error::Error,
+    fmt,
+    sync::{Arc, Condvar, Mutex, MutexGuard, PoisonError},
+    thread,
+    time::{Duration, Instant},
+};
+
+use crate::{Timer, ToDuration};
+
+/// An error indicating that something did not finish before its deadline
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct TimedOut {
+    elapsed: Duration,
+}
+
+impl TimedOut {
+    /// Creates a new [`TimedOut`] error
+    pub fn new(elapsed: Duration) -> TimedOut {
+        TimedOut { elapsed }
+    }
+    /// Gets how much time elapsed before giving up
+    pub fn elapsed(&self) -> Duration {
+        self.elapsed
+    }
+}
+
+impl fmt::Display for TimedOut {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "timed out after {:?}", self.elapsed)
+    }
+}
+
+impl Error for TimedOut {}
+
+/**
+Repeatedly check a condition, sleeping between checks, until it is true or the timeout elapses
+
+Sleeps never extend past the timeout. The condition is always checked at least once.
+
+# Example
+```no_run
+use eggtimer::poll_until;
+use std::net::TcpStream;
+
+poll_until(10.0, 0.1, || TcpStream::connect("127.0.0.1:8080").is_ok())
+    .expect("server did not start");
+```
+*/
+pub fn poll_until<T, I, F>(timeout: T, interval: I, mut condition: F) -> Result<(), TimedOut>
+where
+    T: ToDuration,
+    I: ToDuration,
+    F: FnMut() -> bool,
+{
+    let timer = Timer::set(timeout);
+    let interval = interval.to_duration();
+    loop {
+        if condition() {
+            return Ok(());
+        }
+        match timer.duration_left() {
+            Some(left) => thread::sleep(interval.min(left)),
+            None => return Err(TimedOut::new(timer.duration())),
+        }
+    }
+}
+
+/**
+Waits on a [`Condvar`] while a condition is true, giving up when the [`Timer`] is ready
+
+The time left is recomputed after every wakeup, so spurious wakeups do not extend the wait.
+The condition is always checked at least once. The guard is returned either way,
+along with whether the condition became false in time.
+
+# Example
+```
+use std::sync::{Arc, Condvar, Mutex};
+use eggtimer::{wait_while_with_timer, Timer};
+
+let pair = Arc::new((Mutex::new(false), Condvar::new()));
+let worker_pair = Arc::clone(&pair);
+std::thread::spawn(move || {
+    *worker_pair.0.lock().unwrap() = true;
+    worker_pair.1.notify_one();
+});
+
+let (lock, condvar) = &*pair;
+let timer = Timer::set(5.0);
+let (guard, result) = wait_while_with_timer(condvar, lock.lock().unwrap(), &timer, |done| !*done);
+assert!(*guard);
+assert!(result.is_ok());
+```
+*/
+pub fn wait_while_with_timer<'a, T, F>(
+    condvar: &Condvar,
+    mut guard: MutexGuard<'a, T>,
+    timer: &Timer,
+    mut condition: F,
+) -> (MutexGuard<'a, T>, Result<(), TimedOut>)
+where
+    F: FnMut(&mut T) -> bool,
+{
+    while condition(&mut guard) {
+        match timer.duration_left() {
+            Some(left) => {
+                guard = condvar
+                    .wait_timeout(guard, left)
+                    .unwrap_or_else(PoisonError::into_inner)
+                    .0
+            }
+            None => return (guard, Err(TimedOut::new(timer.duration()))),
+        }
+    }
+    (guard, Ok(()))
+}
+
+/// How long to wait between attempts when retrying
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub enum Backoff {
+    /// Always wait the same amount of time
+    Fixed(Duration),
+    /// Wait longer after each failed attempt
+    Exponential {
+        /// The time to wait after the first failed attempt
+        initial: Duration,
+        /// The number the wait time is multiplied by after each failed attempt
+        factor: f64,
+        /// The maximum time to wait
+        max: Duration,
+    },
+}
+
+impl Backoff {
+    /// Creates a new [`Backoff::Fixed`] with the given number of floating-point seconds
+    pub fn fixed<D: ToDuration>(time: D) -> Backoff {
+        Backoff::Fixed(time.to_duration())
+    }
+    /// Creates a new [`Backoff::Exponential`] that starts at the given number of floating-point seconds,
+    /// doubles after each failed attempt, and never exceeds the given maximum
+    pub fn exponential<D: ToDuration, M: ToDuration>(initial: D, max: M) -> Backoff {
+        Backoff::Exponential {
+            initial: initial.to_duration(),
+            factor: 2.0,
+            max: max.to_duration(),
+        }
+    }
+    /// Gets the time to wait after the given number of failed attempts
+    ///
+    /// `failures` should be at least 1.
+    pub fn delay(&self, failures: u32) -> Duration {
+        match *self {
+            Backoff::Fixed(delay) => delay,
+            Backoff::Exponential {
+                initial,
+                factor,
+                max,
+            } => {
+                let exponent = failures.saturating_sub(1).min(i32::MAX as u32) as i32;
+                let secs = initial.as_secs_f64() * factor.powi(exponent);
+                if secs.is_finite() && secs < max.as_secs_f64() {
+                    Duration::from_secs_f64(secs)
+                } else {
+                    max
+                }
+            }
+        }
+    }
+}
+
+/// Limits on how a function is retried by [`retry`]
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct RetryPolicy {
+    max_attempts: u32,
+    deadline: Duration,
+    backoff: Backoff,
+}
+
+impl RetryPolicy {
+    /// Creates a new [`RetryPolicy`]
+    ///
+    /// The function will be called at most `max_attempts` times, and will not be
+    /// retried if waiting for the next attempt would go past the given number of
+    /// floating-point seconds since the first attempt.
+    pub fn new<D: ToDuration>(max_attempts: u32, deadline: D, backoff: Backoff) -> RetryPolicy {
+        RetryPolicy {
+            max_attempts,
+            deadline: deadline.to_duration(),
+            backoff,
+        }
+    }
+    /// Gets the maximum number of times the function will be called
+    pub fn max_attempts(&self) -> u32 {
+        self.max_attempts
+    }
+    /// Gets the maximum time since the first attempt that retries may start
+    pub fn deadline(&self) -> Duration {
+        self.deadline
+    }
+    /// Gets the [`Backoff`] that determines how long to wait between attempts
+    pub fn backoff(&self) -> Backoff {
+        self.backoff
+    }
+}
+
+/**
+Call a function until it succeeds, following the given [`RetryPolicy`]
+
+Between failed attempts, the current thread sleeps according to the policy's [`Backoff`].
+If the attempts run out, or the next attempt would start after the policy's deadline,
+the error from the last attempt is returned.
+
+# Example
+```no_run
+use eggtimer::{retry, Backoff, RetryPolicy};
+use std::net::TcpStream;
+
+let policy = RetryPolicy::new(5, 10.0, Backoff::exponential(0.1, 2.0));
+let stream = retry(policy, || TcpStream::connect("127.0.0.1:8080"))
+    .expect("could not connect");
+```
+*/
+pub fn retry<F, T, E>(policy: RetryPolicy, mut f: F) -> Result<T, E>
+where
+    F: FnMut() -> Result<T, E>,
+{
+    let timer = Timer::set(policy.deadline);
+    let mut failures = 0;
+    loop {
+        let error = match f() {
+            Ok(value) => return Ok(value),
+            Err(error) => error,
+        };
+        failures += 1;
+        if failures >= policy.max_attempts {
+            return Err(error);
+        }
+        let delay = policy.backoff.delay(failures);
+        match timer.duration_left() {
+            Some(left) if delay <= left => thread::sleep(delay),
+            _ => return Err(error),
+        }
+    }
+}
+
+/// The state shared between a [`Sleeper`] and its [`Waker`]s
+#[derive(Debug, Default)]
+struct SleepState {
+    woken: Mutex<bool>,
+    condvar: Condvar,
+}
+
+/**
+Blocks the current thread for a time, unless woken early by one of its [`Waker`]s
+
+This is useful for timing loops that need to shut down promptly.
+A wake that happens while the [`Sleeper`] is not sleeping is not lost:
+the next sleep returns immediately.
+
+# Example
+```
+use eggtimer::Sleeper;
+
+let sleeper = Sleeper::new();
+let waker = sleeper.waker();
+let worker = std::thread::spawn(move || {
+    while !sleeper.sleep(60.0) {
+        // Do periodic work
+    }
+});
+waker.wake();
+worker.join().unwrap();
+```
+*/
+#[derive(Debug, Default)]
+pub struct Sleeper {
+    state: Arc<SleepState>,
+}
+
+/// A handle that wakes a [`Sleeper`] from another thread
+///
+/// This is created by [`Sleeper::waker`].
+#[derive(Debug, Clone)]
+pub struct Waker {
+    state: Arc<SleepState>,
+}
+
+impl Sleeper {
+    /// Creates a new [`Sleeper`]
+    pub fn new() -> Sleeper {
+        Sleeper::default()
+    }
+    /// Gets a [`Waker`] that can wake this [`Sleeper`]
+    pub fn waker(&self) -> Waker {
+        Waker {
+            state: Arc::clone(&self.state),
+        }
+    }
+    /// Blocks the current thread for the given number of floating-point seconds or until woken
+    ///
+    /// Returns `true` if it was woken and `false` if the time ran out.
+    pub fn sleep<D: ToDuration>(&self, time: D) -> bool {
+        self.sleep_for(&Timer::set(time))
+    }
+    /// Blocks the current thread until the given [`Instant`] or until woken
+    ///
+    /// Returns `true` if it was woken and `false` if the time ran out.
+    pub fn sleep_until(&self, deadline: Instant) -> bool {
+        self.sleep_for(&Timer::until(deadline))
+    }
+    fn sleep_for(&self, timer: &Timer) -> bool {
+        let woken = self
+            .state
+            .woken
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        let (mut woken, _) =
+            wait_while_with_timer(&self.state.condvar, woken, timer, |woken| !*woken);
+        std::mem::replace(&mut *woken, false)
+    }
+}
+
+impl Waker {
+    /// Wakes the [`Sleeper`]
+    ///
+    /// If it is not sleeping, its next sleep returns immediately.
+    pub fn wake(&self) {
+        *self
+            .state
+            .woken
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner) = true;
+        self.state.condvar.notify_all();
+    }
+}
@@ -0,0 +1,451 @@
+use std::{
+    cmp::Ordering,
+    collections::BTreeMap,
+    future::{poll_fn, Future, IntoFuture},
+    hash::{Hash, Hasher},
+    pin::{pin, Pin},
+    sync::{Mutex, MutexGuard, OnceLock, PoisonError},
+    task::{Context, Poll, Waker},
+    thread,
+    time::Instant,
+};
+
+use crate::{alarm::Alarm, TimedOut, Timer, ToDuration};
+
+/// The wakers waiting on the waker thread, keyed by deadline and registration id
+#[derive(Default)]
+struct Entries {
+    wakers: BTreeMap<(Instant, u64), Waker>,
+    next_id: u64,
+}
+
+impl Entries {
+    fn insert(&mut self, deadline: Instant, waker: Waker) -> (Instant, u64) {
+        let key = (deadline, self.next_id);
+        self.next_id += 1;
+        self.wakers.insert(key, waker);
+        key
+    }
+    fn earliest(&self) -> Option<Instant> {
+        self.wakers.keys().next().map(|&(deadline, _)| deadline)
+    }
+}
+
+/// The state shared with the waker thread
+struct Shared {
+    entries: Mutex<Entries>,
+    alarm: Alarm,
+}
+
+static SHARED: OnceLock<Shared> = OnceLock::new();
+
+/// Gets the shared state, spawning the waker thread if this is the first use
+fn shared() -> &'static Shared {
+    SHARED.get_or_init(|| {
+        thread::Builder::new()
+            .name("eggtimer-waker".into())
+            .spawn(run)
+            .expect("failed to spawn eggtimer waker thread");
+        Shared {
+            entries: Mutex::new(Entries::default()),
+            alarm: Alarm::new(),
+        }
+    })
+}
+
+impl Shared {
+    fn lock(&self) -> MutexGuard<'_, Entries> {
+        self.entries.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+    /// Wakes the waker thread if the earliest deadline moved earlier
+    fn notify_if_earlier(&self, earliest: Option<Instant>, deadline: Instant) {
+        if earliest.is_none_or(|earliest| deadline < earliest) {
+            self.alarm.notify();
+        }
+    }
+}
+
+/// A single [`Waker`] waiting on the waker thread
+///
+/// Registering again replaces the previous [`Waker`] and deadline,
+/// and dropping the [`Registration`] removes it.
+#[derive(Debug, Default)]
+pub(crate) struct Registration {
+    key: Option<(Instant, u64)>,
+}
+
+impl Registration {
+    /// Wakes the given [`Waker`] at or shortly after the deadline instead of any previously registered one
+    pub(crate) fn register(&mut self, deadline: Instant, waker: &Waker) {
+        if deadline <= Instant::now() {
+            self.clear();
+            waker.wake_by_ref();
+            return;
+        }
+        let shared = shared();
+        let mut entries = shared.lock();
+        if let Some(key) = self.key {
+            if key.0 == deadline {
+                // The entry is missing if the waker thread already woke it
+                if let Some(registered) = entries.wakers.get_mut(&key) {
+                    if !registered.will_wake(waker) {
+                        *registered = waker.clone();
+                    }
+                    return;
+                }
+            } else {
+                entries.wakers.remove(&key);
+            }
+        }
+        let earliest = entries.earliest();
+        self.key = Some(entries.insert(deadline, waker.clone()));
+        drop(entries);
+        shared.notify_if_earlier(earliest, deadline);
+    }
+    /// Removes the registered [`Waker`], if any
+    pub(crate) fn clear(&mut self) {
+        if let (Some(key), Some(shared)) = (self.key.take(), SHARED.get()) {
+            shared.lock().wakers.remove(&key);
+        }
+    }
+}
+
+impl Drop for Registration {
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+
+#[cfg(all(feature = "async-std", not(feature = "async-io")))]
+type BoxedSleep = Pin<Box<dyn Future<Output = ()> + Send>>;
+
+/**
+Wakes a task at a deadline that may change between polls
+
+With the `async-io` feature, this uses an `async_io::Timer`. Otherwise, with the `async-std` feature,
+it uses `async-std`'s timers. Otherwise it uses a [`Registration`] with the shared waker thread.
+*/
+#[derive(Default)]
+pub(crate) struct DeadlineTimer {
+    #[cfg(feature = "async-io")]
+    timer: Option<(Instant, async_io::Timer)>,
+    #[cfg(all(feature = "async-std", not(feature = "async-io")))]
+    timer: Option<(Instant, BoxedSleep)>,
+    #[cfg(not(any(feature = "async-io", feature = "async-std")))]
+    registration: Registration,
+}
+
+impl DeadlineTimer {
+    /// Wakes the task in the context at or shortly after the deadline instead of any previous deadline
+    pub(crate) fn poll_until(&mut self, deadline: Instant, cx: &mut Context<'_>) {
+        #[cfg(feature = "async-io")]
+        let ready = {
+            let timer = match &mut self.timer {
+                Some((current, timer)) => {
+                    if *current != deadline {
+                        *current = deadline;
+                        timer.set_at(deadline);
+                    }
+                    timer
+                }
+                None => {
+                    &mut self
+                        .timer
+                        .insert((deadline, async_io::Timer::at(deadline)))
+                        .1
+                }
+            };
+            Pin::new(timer).poll(cx).is_ready()
+        };
+        #[cfg(all(feature = "async-std", not(feature = "async-io")))]
+        let ready = {
+            let timer = match &mut self.timer {
+                Some((current, timer)) if *current == deadline => timer,
+                timer => {
+                    let left = deadline.saturating_duration_since(Instant::now());
+                    &mut timer
+                        .insert((deadline, Box::pin(async_std::task::sleep(left))))
+                        .1
+                }
+            };
+            timer.as_mut().poll(cx).is_ready()
+        };
+        #[cfg(any(feature = "async-io", feature = "async-std"))]
+        if ready {
+            self.timer = None;
+            cx.waker().wake_by_ref();
+        }
+        #[cfg(not(any(feature = "async-io", feature = "async-std")))]
+        self.registration.register(deadline, cx.waker());
+    }
+    /// Stops waiting for the deadline
+    pub(crate) fn clear(&mut self) {
+        #[cfg(any(feature = "async-io", feature = "async-std"))]
+        {
+            self.timer = None;
+        }
+        #[cfg(not(any(feature = "async-io", feature = "async-std")))]
+        self.registration.clear();
+    }
+}
+
+/// The waker thread's loop
+fn run() {
+    let shared = shared();
+    let mut entries = shared.lock();
+    loop {
+        let now = Instant::now();
+        while let Some(entry) = entries.wakers.first_entry() {
+            if entry.key().0 > now {
+                break;
+            }
+            entry.remove().wake();
+        }
+        let timeout = entries
+            .earliest()
+            .map(|deadline| deadline.saturating_duration_since(now));
+        entries = shared.alarm.wait(&shared.entries, entries, timeout);
+    }
+}
+
+/**
+A future that completes at a deadline
+
+This is created by [`sleep`], [`sleep_until`], and [`Timer::ready`].
+It is woken by a shared background thread, so it works with any async runtime.
+*/
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct Sleep {
+    deadline: Instant,
+    registration: Registration,
+}
+
+impl Sleep {
+    /// Gets the [`Instant`] at which the [`Sleep`] completes
+    pub fn deadline(&self) -> Instant {
+        self.deadline
+    }
+}
+
+impl Future for Sleep {
+    type Output = ();
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        if Instant::now() >= self.deadline {
+            self.registration.clear();
+            Poll::Ready(())
+        } else {
+            let deadline = self.deadline;
+            self.registration.register(deadline, cx.waker());
+            Poll::Pending
+        }
+    }
+}
+
+impl Clone for Sleep {
+    /// Creates a [`Sleep`] with the same deadline that has not been polled yet
+    fn clone(&self) -> Self {
+        sleep_until(self.deadline)
+    }
+}
+
+impl PartialEq for Sleep {
+    fn eq(&self, other: &Self) -> bool {
+        self.deadline == other.deadline
+    }
+}
+
+impl Eq for Sleep {}
+
+impl PartialOrd for Sleep {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Sleep {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.deadline.cmp(&other.deadline)
+    }
+}
+
+impl Hash for Sleep {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.deadline.hash(state);
+    }
+}
+
+/**
+Waits asynchronously for the given number of floating-point seconds
+
+This does not depend on any particular async runtime.
+
+# Example
+```
+use eggtimer::sleep;
+
+async fn delayed_greeting() {
+    sleep(0.5).await;
+    println!("Hello!");
+}
+```
+*/
+pub fn sleep<D: ToDuration>(time: D) -> Sleep {
+    sleep_until(Instant::now() + time.to_duration())
+}
+
+/// Waits asynchronously until the given [`Instant`]
+///
+/// This does not depend on any particular async runtime.
+pub fn sleep_until(deadline: Instant) -> Sleep {
+    Sleep {
+        deadline,
+        registration: Registration::default(),
+    }
+}
+
+impl Timer {
+    /// Waits asynchronously until the [`Timer`] is ready
+    ///
+    /// This does not depend on any particular async runtime.
+    /// A [`Timer`] can also be awaited directly.
+    pub fn ready(&self) -> Sleep {
+        sleep_until(self.ends_at())
+    }
+}
+
+impl IntoFuture for Timer {
+    type Output = ();
+    type IntoFuture = Sleep;
+    fn into_future(self) -> Sleep {
+        self.ready()
+    }
+}
+
+/**
+Runs a future, giving up if it does not complete within the given number of floating-point seconds
+
+If the future times out, it is dropped and the returned [`TimedOut`] reports how long it ran.
+This does not depend on any particular async runtime.
+
+# Example
+```
+use eggtimer::{sleep, timeout};
+
+async fn slow() {
+    let result = timeout(0.1, sleep(10.0)).await;
+    assert!(result.is_err());
+}
+```
+*/
+pub async fn timeout<D, F>(time: D, fut: F) -> Result<F::Output, TimedOut>
+where
+    D: ToDuration,
+    F: Future,
+{
+    let start = Instant::now();
+    let mut fut = pin!(fut);
+    let mut deadline = sleep(time);
+    poll_fn(|cx| {
+        if let Poll::Ready(output) = fut.as_mut().poll(cx) {
+            Poll::Ready(Ok(output))
+        } else if Pin::new(&mut deadline).poll(cx).is_ready() {
+            Poll::Ready(Err(TimedOut::new(start.elapsed())))
+        } else {
+            Poll::Pending
+        }
+    })
+    .await
+}
+
+/**
+Waits for whichever of several [`Sleep`]s completes first and returns its index
+
+This is useful for juggling several deadlines at once, like keepalive, handshake, and idle timeouts.
+If several are complete, the lowest index is returned.
+
+# Panics
+
+Panics if `sleeps` is empty
+
+# Example
+```
+use eggtimer::{await_any, Timer};
+
+async fn wait_for_deadline(keepalive: Timer, idle: Timer) {
+    match await_any(&mut [keepalive.ready(), idle.ready()]).await {
+        0 => println!("send keepalive"),
+        _ => println!("close idle connection"),
+    }
+}
+```
+*/
+pub async fn await_any(sleeps: &mut [Sleep]) -> usize {
+    assert!(!sleeps.is_empty(), "await_any requires at least one Sleep");
+    poll_fn(|cx| {
+        sleeps
+            .iter_mut()
+            .position(|sleep| Pin::new(sleep).poll(cx).is_ready())
+            .map_or(Poll::Pending, Poll::Ready)
+    })
+    .await
+}
+
+/// Waits for all of several [`Sleep`]s to complete
+///
+/// This completes immediately if `sleeps` is empty.
+pub async fn await_all(sleeps: &mut [Sleep]) {
+    poll_fn(|cx| {
+        let mut pending = false;
+        for sleep in sleeps.iter_mut() {
+            pending |= Pin::new(sleep).poll(cx).is_pending();
+        }
+        if pending {
+            Poll::Pending
+        } else {
+            Poll::Ready(())
+        }
+    })
+    .await
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+
+    struct Noop;
+
+    impl std::task::Wake for Noop {
+        fn wake(self: Arc<Self>) {}
+    }
+
+    /// A waker that does nothing, since `Waker::noop` is newer than the crate's minimum Rust version
+    pub(crate) fn noop_waker() -> Waker {
+        Waker::from(Arc::new(Noop))
+    }
+
+    fn registered() -> usize {
+        shared().lock().wakers.len()
+    }
+
+    #[test]
+    fn repeated_polls_do_not_accumulate_wakers() {
+        let waker = noop_waker();
+        let mut cx = Context::from_waker(&waker);
+        let before = registered();
+        let mut sleep = sleep(3600.0);
+        for _ in 0..100 {
+            assert!(Pin::new(&mut sleep).poll(&mut cx).is_pending());
+        }
+        assert_eq!(registered(), before + 1);
+        drop(sleep);
+        assert_eq!(registered(), before);
+        for _ in 0..100 {
+            let mut fut = pin!(timeout(3600.0, std::future::pending::<()>()));
+            assert!(fut.as_mut().poll(&mut cx).is_pending());
+        }
+        assert_eq!(registered(), before);
+    }
+}